@@ -0,0 +1,22 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "capi")]
+    header();
+}
+
+/// Writes C header of the C API to `$OUT_DIR/savan.h`, leaving the source tree untouched;
+/// `include/savan.h` is regenerated explicitly via
+/// `cbindgen --config cbindgen.toml --output include/savan.h`.
+#[cfg(feature = "capi")]
+fn header() {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+    let out = std::env::var("OUT_DIR").expect("set by cargo");
+    println!("cargo:rerun-if-changed=src/capi");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config =
+        cbindgen::Config::from_file(format!("{dir}/cbindgen.toml")).expect("valid cbindgen.toml");
+    cbindgen::generate_with_config(&dir, config)
+        .expect("generated header")
+        .write_to_file(format!("{out}/savan.h"));
+}
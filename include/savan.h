@@ -0,0 +1,263 @@
+#ifndef SAVAN_H
+#define SAVAN_H
+
+/* Generated by cbindgen from src/capi via `cbindgen --config cbindgen.toml --output include/savan.h`; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status codes, mirroring variants of [NavigatorError](crate::nav::errors::NavigatorError).
+ */
+typedef enum SavanStatus {
+  SAVAN_STATUS_OK = 0,
+  /**
+   * Error within clingo.
+   */
+  SAVAN_STATUS_CLINGO = 1,
+  /**
+   * Solver returned no result.
+   */
+  SAVAN_STATUS_NONE = 2,
+  /**
+   * Navigator lost its solver.
+   */
+  SAVAN_STATUS_NO_CONTROL = 3,
+  SAVAN_STATUS_IO = 4,
+  SAVAN_STATUS_INVALID_INPUT = 5,
+  SAVAN_STATUS_PARSE = 6,
+  /**
+   * A required pointer was null.
+   */
+  SAVAN_STATUS_NULL_POINTER = 7,
+  /**
+   * A string was not valid UTF-8.
+   */
+  SAVAN_STATUS_INVALID_UTF8 = 8,
+  /**
+   * Rust code panicked.
+   */
+  SAVAN_STATUS_PANIC = 9,
+  /**
+   * Solving was interrupted.
+   */
+  SAVAN_STATUS_INTERRUPTED = 10,
+} SavanStatus;
+
+/**
+ * Consequences computed by [savan_consequences](savan_consequences).
+ */
+typedef enum SavanConsequences {
+  SAVAN_CONSEQUENCES_BRAVE = 0,
+  SAVAN_CONSEQUENCES_CAUTIOUS = 1,
+  SAVAN_CONSEQUENCES_FACET_INDUCING = 2,
+} SavanConsequences;
+
+/**
+ * Weighting functions, see [Weight](crate::nav::weights::Weight).
+ */
+typedef enum SavanWeight {
+  SAVAN_WEIGHT_ANSWER_SET_COUNTING = 0,
+  SAVAN_WEIGHT_FACET_COUNTING = 1,
+} SavanWeight;
+
+/**
+ * Opaque list of models, each a list of atoms.
+ */
+typedef struct SavanModels SavanModels;
+
+/**
+ * Opaque navigator handle.
+ */
+typedef struct SavanNavigator SavanNavigator;
+
+/**
+ * Opaque list of strings.
+ */
+typedef struct SavanStrings SavanStrings;
+
+/**
+ * Opaque list of facets and their weights.
+ */
+typedef struct SavanWeights SavanWeights;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns message of the last error on the calling thread, or null.
+ *
+ * The message stays valid until the next failing call on the same thread.
+ */
+const char *savan_last_error(void);
+
+/**
+ * Constructs navigator over **program**, passing **args** to clingo.
+ *
+ * # Safety
+ * **program** must be a valid C string, and **args** an array of **args_len** C strings.
+ */
+enum SavanStatus savan_navigator_new(const char *program,
+                                     const char *const *args,
+                                     size_t args_len,
+                                     struct SavanNavigator **out);
+
+/**
+ * Releases **nav**; null is ignored.
+ *
+ * # Safety
+ * **nav** must stem from [savan_navigator_new](savan_navigator_new), and not be used afterwards.
+ */
+void savan_navigator_free(struct SavanNavigator *nav);
+
+/**
+ * Computes **kind** of consequences under **route**, while projecting onto shown atoms, if
+ * **project** is set.
+ *
+ * **kind** is a [SavanConsequences](SavanConsequences) value; others fail with
+ * `SAVAN_STATUS_INVALID_INPUT`.
+ *
+ * # Safety
+ * **nav** must be a valid navigator, and **route** an array of **route_len** C strings.
+ */
+enum SavanStatus savan_consequences(struct SavanNavigator *nav,
+                                    const char *const *route,
+                                    size_t route_len,
+                                    uint32_t kind,
+                                    bool project,
+                                    struct SavanStrings **out);
+
+/**
+ * Computes count of **weight** under **route**.
+ *
+ * **weight** is a [SavanWeight](SavanWeight) value; others fail with
+ * `SAVAN_STATUS_INVALID_INPUT`.
+ *
+ * # Safety
+ * **nav** must be a valid navigator, **route** an array of **route_len** C strings, and **out**
+ * valid for writes.
+ */
+enum SavanStatus savan_count(struct SavanNavigator *nav,
+                             const char *const *route,
+                             size_t route_len,
+                             uint32_t weight,
+                             bool project,
+                             size_t *out);
+
+/**
+ * Computes weights of facets under **route** w.r.t. **weight**.
+ *
+ * **weight** is a [SavanWeight](SavanWeight) value; others fail with
+ * `SAVAN_STATUS_INVALID_INPUT`.
+ *
+ * # Safety
+ * **nav** must be a valid navigator, and **route** an array of **route_len** C strings.
+ */
+enum SavanStatus savan_facet_weights(struct SavanNavigator *nav,
+                                     const char *const *route,
+                                     size_t route_len,
+                                     uint32_t weight,
+                                     bool project,
+                                     struct SavanWeights **out);
+
+/**
+ * Enumerates up to **limit** models under **route**; all models, if **limit** is zero.
+ *
+ * # Safety
+ * **nav** must be a valid navigator, and **route** an array of **route_len** C strings.
+ */
+enum SavanStatus savan_models(struct SavanNavigator *nav,
+                              const char *const *route,
+                              size_t route_len,
+                              size_t limit,
+                              struct SavanModels **out);
+
+/**
+ * Returns number of strings in **xs**.
+ *
+ * # Safety
+ * **xs** must be a valid list or null.
+ */
+size_t savan_strings_len(const struct SavanStrings *xs);
+
+/**
+ * Returns string at **i** in **xs**, or null if out of range; valid as long as **xs**.
+ *
+ * # Safety
+ * **xs** must be a valid list or null.
+ */
+const char *savan_strings_get(const struct SavanStrings *xs, size_t i);
+
+/**
+ * Releases **xs**; null is ignored.
+ *
+ * # Safety
+ * **xs** must stem from this library, and not be used afterwards.
+ */
+void savan_strings_free(struct SavanStrings *xs);
+
+/**
+ * Returns number of facets in **ws**.
+ *
+ * # Safety
+ * **ws** must be a valid list or null.
+ */
+size_t savan_weights_len(const struct SavanWeights *ws);
+
+/**
+ * Returns facet at **i** in **ws**, or null if out of range; valid as long as **ws**.
+ *
+ * # Safety
+ * **ws** must be a valid list or null.
+ */
+const char *savan_weights_facet(const struct SavanWeights *ws, size_t i);
+
+/**
+ * Returns weight of facet at **i** in **ws**, or zero if out of range.
+ *
+ * # Safety
+ * **ws** must be a valid list or null.
+ */
+size_t savan_weights_weight(const struct SavanWeights *ws, size_t i);
+
+/**
+ * Releases **ws**; null is ignored.
+ *
+ * # Safety
+ * **ws** must stem from this library, and not be used afterwards.
+ */
+void savan_weights_free(struct SavanWeights *ws);
+
+/**
+ * Returns number of models in **ms**.
+ *
+ * # Safety
+ * **ms** must be a valid list or null.
+ */
+size_t savan_models_len(const struct SavanModels *ms);
+
+/**
+ * Returns atoms of model at **i** in **ms**, or null if out of range; valid as long as **ms**.
+ *
+ * # Safety
+ * **ms** must be a valid list or null.
+ */
+const struct SavanStrings *savan_models_get(const struct SavanModels *ms, size_t i);
+
+/**
+ * Releases **ms**; null is ignored.
+ *
+ * # Safety
+ * **ms** must stem from this library, and not be used afterwards.
+ */
+void savan_models_free(struct SavanModels *ms);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SAVAN_H */
@@ -0,0 +1,797 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use clingo::Symbol;
+use savan::lex;
+use savan::nav::backend::Optimization;
+use savan::nav::diagnosis::{Diagnose, Diagnosis, Explanation};
+use savan::nav::errors::{NavigatorError, Result};
+use savan::nav::facets::{Facets, TruthValue};
+use savan::nav::implications::Implications;
+use savan::nav::pareto::Pareto;
+use savan::nav::preferences::{Preferences, Preferred};
+use savan::nav::sampling::Sample;
+use savan::nav::soe::Collect;
+use savan::nav::weights::{facet_weights, facet_weights_projecting, Weight};
+use savan::nav::Navigator;
+use serde_json::json;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[cfg(feature = "repl")]
+mod repl;
+
+#[derive(Parser)]
+#[command(name = "savan", version, about = "answer set navigation.")]
+struct Cli {
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum WeightingFunction {
+    /// Number of answer sets a facet removes.
+    AnswerSets,
+    /// Number of facets a facet removes.
+    Facets,
+}
+
+#[derive(Args)]
+struct Input {
+    /// Logic program files.
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+    /// Facets to navigate along, e.g., 'a, ~b(1)'.
+    #[arg(short, long, default_value = "")]
+    route: String,
+    /// Project onto shown atoms.
+    #[arg(short, long)]
+    project: bool,
+    /// Navigate optimal solutions only.
+    #[arg(long, conflicts_with = "within")]
+    optimal: bool,
+    /// Navigate solutions whose cost is at most this bound, e.g., '3,1' by decreasing priority.
+    #[arg(long, value_delimiter = ',', allow_negative_numbers = true)]
+    within: Option<Vec<i64>>,
+    /// Arguments passed to clingo; defaults to '0'.
+    #[arg(last = true)]
+    clingo_args: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Enumerate solutions under route.
+    Enumerate {
+        #[command(flatten)]
+        input: Input,
+        /// Stop after this many solutions.
+        #[arg(short, long)]
+        number: Option<usize>,
+        /// Enumerate solutions violating the fewest facets of route instead.
+        #[arg(long)]
+        nearest: bool,
+    },
+    /// Draw random solutions under route, uniform only for small numbers of solutions.
+    Sample {
+        #[command(flatten)]
+        input: Input,
+        /// Number of samples.
+        #[arg(short, long, default_value_t = 1)]
+        number: usize,
+        /// Seed of the random number generator.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Print frequencies of these atoms among samples instead, e.g., 'a, b(1)'.
+        #[arg(short, long)]
+        targets: Option<String>,
+    },
+    /// Print brave consequences under route.
+    Brave {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Print cautious consequences under route.
+    Cautious {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Print facet-inducing atoms under route.
+    Facets {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Print weights of facets under route.
+    Weights {
+        #[command(flatten)]
+        input: Input,
+        /// Weighting function.
+        #[arg(short, long, value_enum, default_value_t = WeightingFunction::Facets)]
+        weight: WeightingFunction,
+    },
+    /// Collect solutions under route until all target atoms were observed.
+    Sieve {
+        #[command(flatten)]
+        input: Input,
+        /// Target atoms, e.g., 'a, b(1)'.
+        #[arg(short, long)]
+        targets: String,
+    },
+    /// Explain why route has no solutions, and which facets to drop.
+    Diagnose {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Explain which facets of route make atom true or false in all solutions.
+    Explain {
+        #[command(flatten)]
+        input: Input,
+        /// Atom to explain, e.g., 'b(1)'.
+        #[arg(short, long)]
+        atom: String,
+    },
+    /// Print cost of optimal solutions under route.
+    Optimum {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Enumerate solutions under route honouring as many preferred facets as possible.
+    Prefer {
+        #[command(flatten)]
+        input: Input,
+        /// Preferred facets, e.g., 'a, ~b(1)'.
+        #[arg(long)]
+        preferences: String,
+        /// Stop after this many solutions.
+        #[arg(short, long)]
+        number: Option<usize>,
+    },
+    /// Print Pareto-optimal solutions under route, one per priority as objective.
+    Pareto {
+        #[command(flatten)]
+        input: Input,
+        /// Print costs achievable after activating each facet instead.
+        #[arg(long)]
+        facets: bool,
+    },
+    /// Print implications among facets under route, as DOT unless format is JSON.
+    Implications {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Navigate interactively, starting from route.
+    #[cfg(feature = "repl")]
+    Repl {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Serve navigation sessions via JSON-RPC over HTTP.
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on.
+        #[arg(short, long, default_value = "127.0.0.1:8080")]
+        address: String,
+        /// Seconds after which idle sessions are closed.
+        #[arg(short, long, default_value_t = 600)]
+        ttl: u64,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprint!("error: {e}");
+            let mut source = std::error::Error::source(&e);
+            while let Some(e) = source {
+                eprint!(" {e}");
+                source = e.source();
+            }
+            eprintln!();
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let format = cli.format;
+    match cli.command {
+        Command::Enumerate {
+            input,
+            number,
+            nearest: true,
+        } => {
+            let (mut nav, route) = load(&input)?;
+            let nearest = nav.nearest_solutions(number, route.iter())?;
+            match format {
+                Format::Text => print_violations(&nearest),
+                Format::Json => {
+                    let xs = nearest
+                        .iter()
+                        .map(|(atoms, violated)| json!({ "atoms": atoms, "violated": violated }))
+                        .collect::<Vec<_>>();
+                    println!("{}", json!({ "route": route, "solutions": xs }));
+                }
+            }
+        }
+        Command::Enumerate {
+            input,
+            number,
+            nearest: false,
+        } => {
+            let (mut nav, route) = load(&input)?;
+            let optimum = optimum(&mut nav, &input, &route)?;
+            match format {
+                Format::Text => {
+                    match input.project {
+                        true => nav.enumerate_solutions_projecting(number, route.iter())?,
+                        _ => nav.enumerate_solutions(number, route.iter())?,
+                    };
+                    if let Some(line) = optimum_line(optimum.as_deref()) {
+                        println!("{line}");
+                    }
+                }
+                Format::Json => {
+                    let xs = match input.project {
+                        true => nav.solutions_projecting(number, route.iter())?,
+                        _ => nav.solutions(number, route.iter())?,
+                    };
+                    let xs = xs.into_iter().map(strings).collect::<Vec<_>>();
+                    println!(
+                        "{}",
+                        json!({ "route": route, "solutions": xs, "optimum": optimum })
+                    );
+                }
+            }
+        }
+        Command::Sample {
+            input,
+            number,
+            seed,
+            targets: None,
+        } => {
+            let (mut nav, route) = load(&input)?;
+            let samples = nav.samples(route.iter(), number, input.project, seed)?;
+            match format {
+                Format::Text => samples
+                    .iter()
+                    .enumerate()
+                    .for_each(|(i, s)| println!("sample {}: {}", i + 1, s.join(" "))),
+                Format::Json => println!("{}", json!({ "route": route, "samples": samples })),
+            }
+        }
+        Command::Sample {
+            input,
+            number,
+            seed,
+            targets: Some(targets),
+        } => {
+            let (mut nav, route) = load(&input)?;
+            let targets = lex::parse_facets(&targets)?
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>();
+            let fs = nav.frequencies(route.iter(), &targets, number, input.project, seed)?;
+            match format {
+                Format::Text => fs.iter().for_each(|(a, f)| println!("{f:.2} {a}")),
+                Format::Json => {
+                    let fs = fs
+                        .iter()
+                        .map(|(a, f)| json!({ "atom": a, "frequency": f }))
+                        .collect::<Vec<_>>();
+                    println!("{}", json!({ "route": route, "frequencies": fs }));
+                }
+            }
+        }
+        Command::Brave { input } => {
+            let (mut nav, route) = load(&input)?;
+            let xs = match input.project {
+                true => nav.brave_consequences_projecting(route.iter()),
+                _ => nav.brave_consequences(route.iter()),
+            }
+            .ok_or(NavigatorError::None)?;
+            let optimum = optimum(&mut nav, &input, &route)?;
+            print_atoms(format, "brave", &route, xs, optimum);
+        }
+        Command::Cautious { input } => {
+            let (mut nav, route) = load(&input)?;
+            let xs = match input.project {
+                true => nav.cautious_consequences_projecting(route.iter()),
+                _ => nav.cautious_consequences(route.iter()),
+            }
+            .ok_or(NavigatorError::None)?;
+            let optimum = optimum(&mut nav, &input, &route)?;
+            print_atoms(format, "cautious", &route, xs, optimum);
+        }
+        Command::Facets { input } => {
+            let (mut nav, route) = load(&input)?;
+            let xs = match input.project {
+                true => nav.facet_inducing_atoms_projecting(route.iter()),
+                _ => nav.facet_inducing_atoms(route.iter()),
+            }
+            .ok_or(NavigatorError::None)?;
+            let optimum = optimum(&mut nav, &input, &route)?;
+            print_atoms(format, "facets", &route, xs, optimum);
+        }
+        Command::Weights { input, weight } => {
+            let (mut nav, route) = load(&input)?;
+            let ws = weights(&mut nav, &route, weight, input.project)?;
+            match format {
+                Format::Text => ws.iter().for_each(|(f, w)| println!("{w} {f}")),
+                Format::Json => {
+                    let ws = ws
+                        .iter()
+                        .map(|(f, w)| json!({ "facet": f, "weight": w }))
+                        .collect::<Vec<_>>();
+                    println!("{}", json!({ "route": route, "weights": ws }));
+                }
+            }
+        }
+        Command::Sieve { input, targets } => {
+            let (mut nav, route) = load(&input)?;
+            let targets = lex::parse_facets(&targets)?
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>();
+            match format {
+                Format::Text => nav.sieve(route.iter(), &targets, input.project)?,
+                Format::Json => {
+                    let xs = nav.sieve_solutions(route.iter(), &targets, input.project)?;
+                    let (covered, uncovered): (Vec<_>, Vec<_>) = targets
+                        .iter()
+                        .partition(|t| xs.iter().flatten().any(|a| a == *t));
+                    println!(
+                        "{}",
+                        json!({
+                            "route": route,
+                            "solutions": xs,
+                            "covered": covered,
+                            "uncovered": uncovered
+                        })
+                    );
+                }
+            }
+        }
+        Command::Diagnose { input } => {
+            let (mut nav, route) = load(&input)?;
+            let diagnosis = nav.diagnose(route.iter())?;
+            match format {
+                Format::Text => print_diagnosis(diagnosis),
+                Format::Json => {
+                    let (core, corrections) = diagnosis
+                        .map(|d| (Some(d.core), d.corrections))
+                        .unwrap_or_default();
+                    println!(
+                        "{}",
+                        json!({ "route": route, "core": core, "corrections": corrections })
+                    );
+                }
+            }
+        }
+        Command::Explain { input, atom } => {
+            let (mut nav, route) = load(&input)?;
+            let explanation = nav.explain(route.iter(), &atom)?;
+            match format {
+                Format::Text => print_explanation(&atom, explanation),
+                Format::Json => {
+                    let (value, reasons) = match explanation {
+                        Some(e) => (Some(e.value == TruthValue::True), Some(e.reasons)),
+                        None => (None, None),
+                    };
+                    println!(
+                        "{}",
+                        json!({ "route": route, "atom": atom, "value": value, "reasons": reasons })
+                    );
+                }
+            }
+        }
+        Command::Optimum { input } => {
+            let (mut nav, route) = load(&input)?;
+            let cost = nav.optimum(route.iter())?;
+            match format {
+                Format::Text => print_optimum(cost),
+                Format::Json => println!("{}", json!({ "route": route, "optimum": cost })),
+            }
+        }
+        Command::Prefer {
+            input,
+            preferences,
+            number,
+        } => {
+            let (mut nav, route) = load(&input)?;
+            let preferences = lex::parse_facets(&preferences)?
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>();
+            let preferred = nav.preferred(route.iter(), preferences.iter(), number)?;
+            match format {
+                Format::Text => print_preferred(preferred),
+                Format::Json => {
+                    let preferred = preferred.map(|p| {
+                        let solutions = p
+                            .solutions
+                            .iter()
+                            .map(
+                                |(atoms, violated)| json!({ "atoms": atoms, "violated": violated }),
+                            )
+                            .collect::<Vec<_>>();
+                        json!({
+                            "solutions": solutions,
+                            "brave": p.brave,
+                            "cautious": p.cautious,
+                            "unhonoured": p.unhonoured,
+                        })
+                    });
+                    println!("{}", json!({ "route": route, "preferred": preferred }));
+                }
+            }
+        }
+        Command::Pareto {
+            input,
+            facets: false,
+        } => {
+            let (mut nav, route) = load(&input)?;
+            let front = nav.pareto_front(route.iter())?;
+            match format {
+                Format::Text => front
+                    .iter()
+                    .for_each(|s| println!("{}: {}", costs(&s.cost), s.atoms.join(" "))),
+                Format::Json => {
+                    let front = front
+                        .iter()
+                        .map(|s| json!({ "atoms": s.atoms, "cost": s.cost }))
+                        .collect::<Vec<_>>();
+                    println!("{}", json!({ "route": route, "front": front }));
+                }
+            }
+        }
+        Command::Pareto {
+            input,
+            facets: true,
+        } => {
+            let (mut nav, route) = load(&input)?;
+            let effects = nav.pareto_facets(route.iter())?;
+            match format {
+                Format::Text => effects.iter().for_each(|e| {
+                    let front = e.front.iter().map(|c| costs(c)).collect::<Vec<_>>();
+                    println!("{}: {}", e.facet, front.join(" | "))
+                }),
+                Format::Json => {
+                    let effects = effects
+                        .iter()
+                        .map(|e| json!({ "facet": e.facet, "front": e.front }))
+                        .collect::<Vec<_>>();
+                    println!("{}", json!({ "route": route, "facets": effects }));
+                }
+            }
+        }
+        Command::Implications { input } => {
+            let (mut nav, route) = load(&input)?;
+            let graph = nav
+                .implication_graph(route.iter(), input.project)
+                .ok_or(NavigatorError::None)?;
+            match format {
+                Format::Text => print!("{}", graph.to_dot()),
+                Format::Json => println!("{}", graph.to_json()),
+            }
+        }
+        #[cfg(feature = "repl")]
+        Command::Repl { input } => repl::run(input)?,
+        #[cfg(feature = "server")]
+        Command::Serve { address, ttl } => {
+            let mut server =
+                savan::server::Server::bind(address, std::time::Duration::from_secs(ttl))?;
+            if let Some(addr) = server.local_addr() {
+                eprintln!("listening on http://{addr}/rpc");
+            }
+            server.run()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Constructs navigator over program in files of **input**, and validates its route.
+fn load(input: &Input) -> Result<(Navigator, Vec<String>)> {
+    let mut lp = String::new();
+    for file in &input.files {
+        lp.push_str(&std::fs::read_to_string(file)?);
+        lp.push('\n');
+    }
+    let args = match input.clingo_args.is_empty() {
+        true => vec!["0".to_owned()],
+        _ => input.clingo_args.clone(),
+    };
+    let mut nav = Navigator::new(lp, args)?;
+    nav.optimize(optimization(input))?;
+
+    let mut route = vec![];
+    for facet in lex::parse_facets(&input.route)? {
+        let atom = lex::repr(facet.symbol);
+        if nav.is_known(atom.clone()) != Some(true) {
+            return Err(NavigatorError::InvalidInput(format!("unknown atom {atom}")));
+        }
+        route.push(facet.to_string());
+    }
+
+    Ok((nav, route))
+}
+
+/// Returns which solutions to navigate according to **input**.
+fn optimization(input: &Input) -> Optimization {
+    match (&input.within, input.optimal) {
+        (Some(bound), _) => Optimization::Within(bound.clone()),
+        (None, true) => Optimization::Optimal,
+        _ => Optimization::Any,
+    }
+}
+
+/// Returns cost of optimal solutions under **route**, if **input** restricts navigation by cost.
+fn optimum(nav: &mut Navigator, input: &Input, route: &[String]) -> Result<Option<Vec<i64>>> {
+    match optimization(input) {
+        Optimization::Any => Ok(None),
+        _ => nav.optimum(route.iter()),
+    }
+}
+
+/// Prints solutions of **preferred** with the preferences they violate.
+fn print_preferred(preferred: Option<Preferred>) {
+    match preferred {
+        None => println!("route has no solutions"),
+        Some(p) => {
+            if !p.unhonoured.is_empty() {
+                println!("unhonoured: {}", p.unhonoured.join(", "));
+            }
+            print_violations(&p.solutions);
+        }
+    }
+}
+
+/// Prints **solutions** with the facets they violate.
+fn print_violations(solutions: &[(Vec<String>, Vec<String>)]) {
+    for (i, (atoms, violated)) in solutions.iter().enumerate() {
+        println!("solution {}: {}", i + 1, atoms.join(" "));
+        if !violated.is_empty() {
+            println!("violates: {}", violated.join(", "));
+        }
+    }
+}
+
+/// Prints **cost** of optimal solutions.
+fn print_optimum(cost: Option<Vec<i64>>) {
+    match cost {
+        None => println!("route has no solutions"),
+        Some(cost) if cost.is_empty() => println!("program has no optimization statements"),
+        Some(cost) => println!("{}", costs(&cost)),
+    }
+}
+
+/// Returns line reporting **optimum** after navigation results in text format, if navigation is
+/// restricted by cost, e.g., `optimum: 3 1`.
+fn optimum_line(optimum: Option<&[i64]>) -> Option<String> {
+    optimum
+        .filter(|cost| !cost.is_empty())
+        .map(|cost| format!("optimum: {}", costs(cost)))
+}
+
+/// Formats **cost** by decreasing priority, e.g., `3 1`.
+fn costs(cost: &[i64]) -> String {
+    cost.iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns weights of facets under **route** w.r.t. **weight**.
+fn weights(
+    nav: &mut Navigator,
+    route: &[String],
+    weight: WeightingFunction,
+    projecting: bool,
+) -> Result<Vec<(String, usize)>> {
+    let mut w = match weight {
+        WeightingFunction::AnswerSets => Weight::AnswerSetCounting,
+        WeightingFunction::Facets => Weight::FacetCounting,
+    };
+    match projecting {
+        true => facet_weights_projecting(&mut w, nav, route),
+        _ => facet_weights(&mut w, nav, route),
+    }
+    .ok_or(NavigatorError::None)
+}
+
+/// Prints conflicting facets of **diagnosis**, and which facets to drop.
+fn print_diagnosis(diagnosis: Option<Diagnosis>) {
+    match diagnosis {
+        None => println!("route has solutions"),
+        Some(d) if d.core.is_empty() => println!("program has no solutions"),
+        Some(d) => {
+            println!("conflict: {}", d.core.join(", "));
+            d.corrections
+                .iter()
+                .for_each(|c| println!("drop: {}", c.join(", ")));
+        }
+    }
+}
+
+/// Prints which facets force **atom** according to **explanation**.
+fn print_explanation(atom: &str, explanation: Option<Explanation>) {
+    match explanation {
+        None => println!("{atom} is undecided, or route has no solutions"),
+        Some(e) => {
+            let value = match e.value {
+                TruthValue::True => "true",
+                _ => "false",
+            };
+            match e.reasons.is_empty() {
+                true => println!("{atom} is {value} in all solutions of the program"),
+                _ => println!("{atom} is {value} due to {}", e.reasons.join(", ")),
+            }
+        }
+    }
+}
+
+fn print_atoms(
+    format: Format,
+    kind: &str,
+    route: &[String],
+    atoms: impl IntoIterator<Item = Symbol>,
+    optimum: Option<Vec<i64>>,
+) {
+    let atoms = strings(atoms.into_iter().collect());
+    match format {
+        Format::Text => {
+            atoms.iter().for_each(|a| println!("{a}"));
+            if let Some(line) = optimum_line(optimum.as_deref()) {
+                println!("{line}");
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            json!({ "route": route, kind: atoms, "optimum": optimum })
+        ),
+    }
+}
+
+/// Converts **symbols** to sorted strings.
+fn strings(symbols: Vec<Symbol>) -> Vec<String> {
+    let mut xs = symbols.into_iter().map(lex::repr).collect::<Vec<_>>();
+    xs.sort();
+
+    xs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes **program** to a file of its own, named after **name**.
+    fn program(name: &str, program: &str) -> Result<PathBuf> {
+        let path = std::env::temp_dir().join(format!("savan-{}-{name}.lp", std::process::id()));
+        std::fs::write(&path, program)?;
+
+        Ok(path)
+    }
+
+    #[test]
+    fn parse_arguments() {
+        let cli = Cli::try_parse_from([
+            "savan",
+            "enumerate",
+            "a.lp",
+            "b.lp",
+            "--route",
+            "a, ~b(1)",
+            "-n",
+            "2",
+            "--within",
+            "3,-1",
+            "--format",
+            "json",
+            "--",
+            "0",
+            "--opt-mode=optN",
+        ])
+        .unwrap();
+        assert!(cli.format == Format::Json);
+        match cli.command {
+            Command::Enumerate {
+                input,
+                number,
+                nearest,
+            } => {
+                assert_eq!(
+                    input.files,
+                    vec![PathBuf::from("a.lp"), PathBuf::from("b.lp")]
+                );
+                assert_eq!(input.route, "a, ~b(1)");
+                assert_eq!(number, Some(2));
+                assert!(!nearest && !input.project && !input.optimal);
+                assert_eq!(input.within, Some(vec![3, -1]));
+                assert_eq!(input.clingo_args, vec!["0", "--opt-mode=optN"]);
+                assert_eq!(optimization(&input), Optimization::Within(vec![3, -1]));
+            }
+            _ => panic!("expected enumerate"),
+        }
+
+        let cli = Cli::try_parse_from(["savan", "sieve", "a.lp", "-p", "-t", "a, b"]).unwrap();
+        assert!(cli.format == Format::Text);
+        match cli.command {
+            Command::Sieve { input, targets } => {
+                assert!(input.project);
+                assert_eq!(targets, "a, b");
+                assert_eq!(optimization(&input), Optimization::Any);
+            }
+            _ => panic!("expected sieve"),
+        }
+
+        for args in [
+            vec!["savan", "brave"],
+            vec!["savan", "sieve", "a.lp"],
+            vec!["savan", "brave", "a.lp", "--optimal", "--within", "1"],
+            vec!["savan", "weights", "a.lp", "--weight", "atoms"],
+            vec!["savan", "brave", "a.lp", "--format", "xml"],
+            vec!["savan", "unknown", "a.lp"],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err());
+        }
+    }
+
+    #[test]
+    fn load_routes() -> Result<()> {
+        let path = program("load", "a;b. c;d :- b. e.")?;
+        let file = path.to_string_lossy().into_owned();
+        let input = |route: &str| match Cli::try_parse_from([
+            "savan",
+            "facets",
+            &file,
+            "--route",
+            route,
+            "--optimal",
+        ]) {
+            Ok(Cli {
+                command: Command::Facets { input },
+                ..
+            }) => input,
+            _ => panic!("expected facets"),
+        };
+
+        let (mut nav, route) = load(&input("b, ~ c"))?;
+        assert_eq!(route, vec!["b", "~c"]);
+        assert_eq!(nav.solutions(None, route.iter())?.len(), 1);
+        assert!(matches!(
+            load(&input("b, f")),
+            Err(NavigatorError::InvalidInput(_))
+        ));
+        assert!(load(&input("b,,")).is_err());
+
+        for args in [
+            vec!["savan", "enumerate", &file, "-r", "b"],
+            vec!["savan", "brave", &file, "--format", "json"],
+            vec!["savan", "sieve", &file, "-t", "a, c", "--format", "json"],
+            vec!["savan", "facets", &file, "--optimal"],
+        ] {
+            run(Cli::try_parse_from(args).unwrap())?;
+        }
+        let missing = Cli::try_parse_from(["savan", "brave", "missing.lp"]).unwrap();
+        assert!(matches!(run(missing), Err(NavigatorError::IOError(_))));
+        std::fs::remove_file(path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_optimum_in_text() {
+        assert_eq!(optimum_line(Some(&[3, 1])), Some("optimum: 3 1".to_owned()));
+        assert_eq!(optimum_line(Some(&[-2])), Some("optimum: -2".to_owned()));
+        assert_eq!(optimum_line(Some(&[])), None);
+        assert_eq!(optimum_line(None), None);
+    }
+}
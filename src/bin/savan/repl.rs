@@ -0,0 +1,380 @@
+use super::{
+    load, print_diagnosis, print_explanation, print_optimum, print_preferred, weights, Input,
+    WeightingFunction,
+};
+use clingo::Symbol;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use savan::lex;
+use savan::nav::diagnosis::Diagnose;
+use savan::nav::errors::{NavigatorError, Result};
+use savan::nav::facets::{print_table, Facets};
+use savan::nav::persistence::SavedRoute;
+use savan::nav::preferences::Preferences;
+use savan::nav::preview::Lookahead;
+use savan::nav::redundancy::Redundancy;
+use savan::nav::Navigator;
+use std::path::PathBuf;
+
+const COMMANDS: [&str; 18] = [
+    "activate",
+    "deactivate",
+    "route",
+    "clear",
+    "minimize",
+    "facets",
+    "preview",
+    "show",
+    "weights",
+    "enumerate",
+    "optimum",
+    "prefer",
+    "diagnose",
+    "why",
+    "save",
+    "load",
+    "help",
+    "quit",
+];
+
+const HELP: &str = "\
+activate <facets>      (+) extend route by facets, e.g., '+ a, ~b(1)'
+deactivate <facets>    (-) remove facets from route
+route                  print route
+clear                  remove all facets from route
+minimize               remove facets implied by the rest of route
+facets                 print route and facet-inducing atoms under route
+preview <facet>        print atoms activating facet would force true, false, or leave open
+show <pattern>         print truth values of atoms matching pattern, e.g., 'p(X,1)'
+weights [answer-sets]  print weights of facets under route
+enumerate [n]          print up to n (default 3) solutions under route
+optimum                print cost of optimal solutions under route
+prefer <facets>        print up to 3 solutions honouring as many of facets as possible
+diagnose               explain why route has no solutions, and which facets to drop
+why <atom>             explain which facets make atom true or false in all solutions
+save <file> [notes]    write route and notes to file, as JSON if file ends with '.json'
+load <file>            read route from file saved for the same program
+help                   print this message
+quit                   leave";
+
+/// Completes command names and atoms of the ground program.
+struct AtomCompleter {
+    atoms: Vec<String>,
+}
+impl Completer for AtomCompleter {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .rfind(|c: char| c.is_whitespace() || c == ',' || c == '~')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let word = &line[start..pos];
+        let candidates = match line[..start].trim().is_empty() {
+            true => COMMANDS
+                .iter()
+                .filter(|c| c.starts_with(word))
+                .map(|c| c.to_string())
+                .collect(),
+            _ => self
+                .atoms
+                .iter()
+                .filter(|a| a.starts_with(word))
+                .cloned()
+                .collect(),
+        };
+
+        Ok((start, candidates))
+    }
+}
+impl Hinter for AtomCompleter {
+    type Hint = String;
+}
+impl Highlighter for AtomCompleter {}
+impl Validator for AtomCompleter {}
+impl Helper for AtomCompleter {}
+
+struct Session {
+    nav: Navigator,
+    route: Vec<String>,
+    projecting: bool,
+}
+
+/// Runs interactive navigation over program in files of **input**.
+pub(crate) fn run(input: Input) -> Result<()> {
+    let (nav, route) = load(&input)?;
+    let mut atoms = nav.atoms().collect::<Vec<_>>();
+    atoms.sort();
+    let mut session = Session {
+        nav,
+        route,
+        projecting: input.project,
+    };
+
+    let mut editor = Editor::new().map_err(|e| NavigatorError::InvalidInput(e.to_string()))?;
+    editor.set_helper(Some(AtomCompleter { atoms }));
+    let history = std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".savan_history"));
+    if let Some(path) = &history {
+        let _ = editor.load_history(path);
+    }
+
+    println!("type 'help' for a list of commands.");
+    loop {
+        match editor.readline("savan> ") {
+            Ok(line) => {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let _ = editor.add_history_entry(line);
+                match session.execute(line) {
+                    Ok(true) => continue,
+                    Ok(false) => break,
+                    Err(e) => println!("error: {e}"),
+                }
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(NavigatorError::InvalidInput(e.to_string())),
+        }
+    }
+
+    if let Some(path) = &history {
+        let _ = editor.save_history(path);
+    }
+
+    Ok(())
+}
+
+impl Session {
+    /// Executes **line**, and returns whether to continue.
+    fn execute(&mut self, line: &str) -> Result<bool> {
+        let (command, rest) = line
+            .split_once(char::is_whitespace)
+            .map(|(c, r)| (c, r.trim()))
+            .unwrap_or((line, ""));
+
+        match command {
+            "activate" | "+" => {
+                for facet in self.facets(rest)? {
+                    if !self.route.contains(&facet) {
+                        self.route.push(facet);
+                    }
+                }
+                self.print_facets()?;
+            }
+            "deactivate" | "-" => {
+                let facets = self.facets(rest)?;
+                self.route.retain(|f| !facets.contains(f));
+                self.print_facets()?;
+            }
+            "route" => println!("{}", self.route.join(", ")),
+            "clear" => {
+                self.route.clear();
+                self.print_facets()?;
+            }
+            "minimize" => {
+                self.route = self
+                    .nav
+                    .minimize_route(self.route.iter())
+                    .ok_or(NavigatorError::None)?;
+                self.print_facets()?;
+            }
+            "facets" => self.print_facets()?,
+            "preview" => {
+                let preview = self
+                    .nav
+                    .preview(self.route.iter(), rest, self.projecting)
+                    .ok_or_else(|| NavigatorError::InvalidInput(format!("unknown facet {rest}")))?;
+                println!("true: {}", preview.forced_true.join(" "));
+                println!("false: {}", preview.forced_false.join(" "));
+                println!("open: {}", preview.open.join(" "));
+            }
+            "show" => {
+                let atoms = self.nav.atoms_matching(rest)?;
+                self.print_truth_values(atoms)?;
+            }
+            "weights" => {
+                let weight = match rest {
+                    "answer-sets" | "as" => WeightingFunction::AnswerSets,
+                    _ => WeightingFunction::Facets,
+                };
+                weights(&mut self.nav, &self.route, weight, self.projecting)?
+                    .iter()
+                    .for_each(|(f, w)| println!("{w} {f}"));
+            }
+            "enumerate" => {
+                let n = match rest.is_empty() {
+                    true => 3,
+                    _ => rest
+                        .parse::<usize>()
+                        .map_err(|_| NavigatorError::InvalidInput(rest.to_owned()))?,
+                };
+                self.nav.enumerate_solutions(Some(n), self.route.iter())?;
+            }
+            "optimum" => print_optimum(self.nav.optimum(self.route.iter())?),
+            "prefer" => {
+                let preferences = self.facets(rest)?;
+                let preferred =
+                    self.nav
+                        .preferred(self.route.iter(), preferences.iter(), Some(3))?;
+                print_preferred(preferred);
+            }
+            "diagnose" => print_diagnosis(self.nav.diagnose(self.route.iter())?),
+            "why" => print_explanation(rest, self.nav.explain(self.route.iter(), rest)?),
+            "save" => {
+                let (file, notes) = rest
+                    .split_once(char::is_whitespace)
+                    .map(|(f, n)| (f, Some(n.trim().to_owned())))
+                    .unwrap_or((rest, None));
+                let saved = SavedRoute::new(&self.nav, &self.route, notes);
+                match file.ends_with(".json") {
+                    true => std::fs::write(file, saved.to_json())?,
+                    _ => std::fs::write(file, saved.to_text())?,
+                }
+            }
+            "load" => {
+                let saved = SavedRoute::parse(&std::fs::read_to_string(rest)?)?;
+                self.route = saved.restore(&self.nav)?;
+                if let Some(notes) = &saved.notes {
+                    println!("{notes}");
+                }
+                self.print_facets()?;
+            }
+            "help" => println!("{HELP}"),
+            "quit" | "exit" => return Ok(false),
+            _ => println!("unknown command {command:?}; type 'help' for a list of commands."),
+        }
+
+        Ok(true)
+    }
+
+    /// Parses **exp** as list of facets over atoms of the ground program.
+    fn facets(&self, exp: &str) -> Result<Vec<String>> {
+        let mut facets = vec![];
+        for facet in lex::parse_facets(exp)? {
+            let atom = lex::repr(facet.symbol);
+            if self.nav.is_known(atom.clone()) != Some(true) {
+                return Err(NavigatorError::InvalidInput(format!("unknown atom {atom}")));
+            }
+            facets.push(facet.to_string());
+        }
+
+        Ok(facets)
+    }
+
+    /// Prints atoms of route and facet-inducing atoms under route with their truth values.
+    fn print_facets(&mut self) -> Result<()> {
+        let mut atoms = lex::parse_facets(&self.route.join(", "))?
+            .into_iter()
+            .map(|f| f.symbol)
+            .collect::<Vec<_>>();
+        let facets = match self.projecting {
+            true => self.nav.facet_inducing_atoms_projecting(self.route.iter()),
+            _ => self.nav.facet_inducing_atoms(self.route.iter()),
+        }
+        .ok_or(NavigatorError::None)?;
+        let mut facets = facets.into_iter().collect::<Vec<_>>();
+        facets.sort_by_key(|a| lex::repr(*a));
+        atoms.extend(facets);
+
+        self.print_truth_values(atoms)
+    }
+
+    /// Prints **atoms** with truth values, i.e., whether they are true in all, false in all, or
+    /// undecided among solutions under route.
+    fn print_truth_values(&mut self, atoms: Vec<Symbol>) -> Result<()> {
+        let atoms = atoms.into_iter().map(lex::repr).collect::<Vec<_>>();
+        let table = match self.projecting {
+            true => self.nav.truth_values_projecting(self.route.iter(), &atoms),
+            _ => self.nav.truth_values(self.route.iter(), &atoms),
+        }
+        .ok_or(NavigatorError::None)?;
+        print_table(&table);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyline::history::DefaultHistory;
+
+    fn session(program: &str) -> Result<Session> {
+        Ok(Session {
+            nav: Navigator::new(program, vec!["0".to_owned()])?,
+            route: vec![],
+            projecting: false,
+        })
+    }
+
+    #[test]
+    fn execute_commands() -> Result<()> {
+        let mut session = session("{a;b}. c :- a. c :- b.")?;
+
+        assert!(session.execute("+ a, c")?);
+        assert_eq!(session.route, vec!["a", "c"]);
+        assert!(session.execute("activate a")?);
+        assert_eq!(session.route, vec!["a", "c"]);
+        assert!(session.execute("+ d").is_err());
+        assert_eq!(session.route, vec!["a", "c"]);
+
+        assert!(session.execute("minimize")?);
+        assert_eq!(session.route, vec!["a"]);
+
+        assert!(session.execute("- a")?);
+        assert!(session.route.is_empty());
+
+        let file = std::env::temp_dir().join(format!("savan-{}-repl.lp", std::process::id()));
+        let file = file.to_str().expect("temp dir is valid unicode");
+        assert!(session.execute("+ ~b")?);
+        assert!(session.execute(&format!("save {file} no b here"))?);
+        assert!(session.execute("clear")?);
+        assert!(session.route.is_empty());
+        assert!(session.execute(&format!("load {file}"))?);
+        assert_eq!(session.route, vec!["~b"]);
+        assert_eq!(
+            SavedRoute::parse(&std::fs::read_to_string(file)?)?.notes,
+            Some("no b here".to_owned())
+        );
+        std::fs::remove_file(file)?;
+
+        assert!(session.execute("unknown")?);
+        assert!(!session.execute("quit")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn complete_commands_and_atoms() -> Result<()> {
+        let completer = AtomCompleter {
+            atoms: vec!["p(1)".to_owned(), "p(2)".to_owned(), "q".to_owned()],
+        };
+        let history = DefaultHistory::new();
+        let ctx = Context::new(&history);
+        let complete = |line: &str| completer.complete(line, line.len(), &ctx).unwrap();
+
+        assert_eq!(complete("de"), (0, vec!["deactivate".to_owned()]));
+        assert_eq!(
+            complete("  p"),
+            (2, vec!["preview".to_owned(), "prefer".to_owned()])
+        );
+        assert_eq!(
+            complete("+ q, ~p"),
+            (6, vec!["p(1)".to_owned(), "p(2)".to_owned()])
+        );
+        assert_eq!(complete("why q"), (4, vec!["q".to_owned()]));
+        assert_eq!(complete("show r"), (5, vec![]));
+
+        Ok(())
+    }
+}
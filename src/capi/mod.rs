@@ -0,0 +1,648 @@
+//! C API over [Navigator](crate::nav::Navigator).
+//!
+//! Navigators and results are opaque handles, each released by its own free function. Functions
+//! return a [SavanStatus](SavanStatus), and write results to out-parameters only on success; the
+//! message of the last error on the calling thread is available via
+//! [savan_last_error](savan_last_error). Routes are arrays of facets such as `"~b(1)"`.
+
+use crate::lex;
+use crate::nav::errors::NavigatorError;
+use crate::nav::facets::Facets;
+use crate::nav::weights::{self, facet_weights, facet_weights_projecting, Weight};
+use crate::nav::Navigator;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Status codes, mirroring variants of [NavigatorError](crate::nav::errors::NavigatorError).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavanStatus {
+    Ok = 0,
+    /// Error within clingo.
+    Clingo = 1,
+    /// Solver returned no result.
+    None = 2,
+    /// Navigator lost its solver.
+    NoControl = 3,
+    Io = 4,
+    InvalidInput = 5,
+    Parse = 6,
+    /// A required pointer was null.
+    NullPointer = 7,
+    /// A string was not valid UTF-8.
+    InvalidUtf8 = 8,
+    /// Rust code panicked.
+    Panic = 9,
+    /// Solving was interrupted.
+    Interrupted = 10,
+}
+impl From<&NavigatorError> for SavanStatus {
+    fn from(e: &NavigatorError) -> Self {
+        match e {
+            NavigatorError::Clingo(_) => Self::Clingo,
+            NavigatorError::None => Self::None,
+            NavigatorError::NoControl => Self::NoControl,
+            NavigatorError::IOError(_) => Self::Io,
+            NavigatorError::Interrupted => Self::Interrupted,
+            NavigatorError::InvalidInput(_) => Self::InvalidInput,
+            NavigatorError::Parse(_) => Self::Parse,
+        }
+    }
+}
+
+/// Weighting functions, see [Weight](crate::nav::weights::Weight).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavanWeight {
+    AnswerSetCounting = 0,
+    FacetCounting = 1,
+}
+impl TryFrom<u32> for SavanWeight {
+    type Error = u32;
+
+    fn try_from(w: u32) -> Result<Self, u32> {
+        match w {
+            0 => Ok(Self::AnswerSetCounting),
+            1 => Ok(Self::FacetCounting),
+            _ => Err(w),
+        }
+    }
+}
+impl From<SavanWeight> for Weight {
+    fn from(w: SavanWeight) -> Self {
+        match w {
+            SavanWeight::AnswerSetCounting => Self::AnswerSetCounting,
+            SavanWeight::FacetCounting => Self::FacetCounting,
+        }
+    }
+}
+
+/// Opaque navigator handle.
+pub struct SavanNavigator {
+    nav: Navigator,
+}
+
+/// Opaque list of strings.
+pub struct SavanStrings {
+    items: Vec<CString>,
+}
+
+/// Opaque list of facets and their weights.
+pub struct SavanWeights {
+    facets: Vec<CString>,
+    weights: Vec<usize>,
+}
+
+/// Opaque list of models, each a list of atoms.
+pub struct SavanModels {
+    models: Vec<SavanStrings>,
+}
+
+struct Failure(SavanStatus, String);
+impl From<NavigatorError> for Failure {
+    fn from(e: NavigatorError) -> Self {
+        let mut message = e.to_string();
+        let mut source = std::error::Error::source(&e);
+        while let Some(e) = source {
+            message.push_str(&format!(" {e}"));
+            source = e.source();
+        }
+        Self((&e).into(), message)
+    }
+}
+
+fn null(name: &str) -> Failure {
+    Failure(SavanStatus::NullPointer, format!("{name} is null"))
+}
+
+fn invalid(name: &str, value: u32) -> Failure {
+    Failure(
+        SavanStatus::InvalidInput,
+        format!("{name} {value} is out of range"),
+    )
+}
+
+/// Runs **f**, records its error, and returns its status.
+fn guard(f: impl FnOnce() -> Result<(), Failure>) -> SavanStatus {
+    let (status, message) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return SavanStatus::Ok,
+        Ok(Err(Failure(status, message))) => (status, message),
+        Err(_) => (SavanStatus::Panic, "panicked".to_owned()),
+    };
+    LAST_ERROR.with(|e| {
+        *e.borrow_mut() = CString::new(message.replace('\0', " ")).ok();
+    });
+
+    status
+}
+
+unsafe fn string(s: *const c_char, name: &str) -> Result<String, Failure> {
+    if s.is_null() {
+        return Err(null(name));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map(str::to_owned)
+        .map_err(|e| Failure(SavanStatus::InvalidUtf8, format!("{name}: {e}")))
+}
+
+unsafe fn strings(
+    xs: *const *const c_char,
+    len: usize,
+    name: &str,
+) -> Result<Vec<String>, Failure> {
+    if len == 0 {
+        return Ok(vec![]);
+    }
+    if xs.is_null() {
+        return Err(null(name));
+    }
+    std::slice::from_raw_parts(xs, len)
+        .iter()
+        .map(|x| string(*x, name))
+        .collect()
+}
+
+unsafe fn navigator<'a>(nav: *mut SavanNavigator) -> Result<&'a mut Navigator, Failure> {
+    nav.as_mut()
+        .map(|n| &mut n.nav)
+        .ok_or_else(|| null("navigator"))
+}
+
+fn c_strings(xs: impl IntoIterator<Item = String>) -> SavanStrings {
+    SavanStrings {
+        items: xs
+            .into_iter()
+            .filter_map(|x| CString::new(x).ok())
+            .collect(),
+    }
+}
+
+fn c_atoms(atoms: impl IntoIterator<Item = clingo::Symbol>) -> SavanStrings {
+    let mut atoms = atoms.into_iter().map(lex::repr).collect::<Vec<_>>();
+    atoms.sort();
+
+    c_strings(atoms)
+}
+
+unsafe fn put<T>(out: *mut *mut T, value: T) -> Result<(), Failure> {
+    if out.is_null() {
+        return Err(null("out"));
+    }
+    *out = Box::into_raw(Box::new(value));
+
+    Ok(())
+}
+
+/// Returns message of the last error on the calling thread, or null.
+///
+/// The message stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn savan_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(std::ptr::null(), |e| e.as_ptr()))
+}
+
+/// Constructs navigator over **program**, passing **args** to clingo.
+///
+/// # Safety
+/// **program** must be a valid C string, and **args** an array of **args_len** C strings.
+#[no_mangle]
+pub unsafe extern "C" fn savan_navigator_new(
+    program: *const c_char,
+    args: *const *const c_char,
+    args_len: usize,
+    out: *mut *mut SavanNavigator,
+) -> SavanStatus {
+    guard(|| {
+        let program = string(program, "program")?;
+        let args = strings(args, args_len, "args")?;
+        let nav = Navigator::new(program, args)?;
+        put(out, SavanNavigator { nav })
+    })
+}
+
+/// Releases **nav**; null is ignored.
+///
+/// # Safety
+/// **nav** must stem from [savan_navigator_new](savan_navigator_new), and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn savan_navigator_free(nav: *mut SavanNavigator) {
+    if !nav.is_null() {
+        drop(Box::from_raw(nav));
+    }
+}
+
+/// Consequences computed by [savan_consequences](savan_consequences).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavanConsequences {
+    Brave = 0,
+    Cautious = 1,
+    FacetInducing = 2,
+}
+impl TryFrom<u32> for SavanConsequences {
+    type Error = u32;
+
+    fn try_from(kind: u32) -> Result<Self, u32> {
+        match kind {
+            0 => Ok(Self::Brave),
+            1 => Ok(Self::Cautious),
+            2 => Ok(Self::FacetInducing),
+            _ => Err(kind),
+        }
+    }
+}
+
+/// Computes **kind** of consequences under **route**, while projecting onto shown atoms, if
+/// **project** is set.
+///
+/// **kind** is a [SavanConsequences](SavanConsequences) value; others fail with
+/// `SAVAN_STATUS_INVALID_INPUT`.
+///
+/// # Safety
+/// **nav** must be a valid navigator, and **route** an array of **route_len** C strings.
+#[no_mangle]
+pub unsafe extern "C" fn savan_consequences(
+    nav: *mut SavanNavigator,
+    route: *const *const c_char,
+    route_len: usize,
+    kind: u32,
+    project: bool,
+    out: *mut *mut SavanStrings,
+) -> SavanStatus {
+    guard(|| {
+        let kind = SavanConsequences::try_from(kind).map_err(|k| invalid("kind", k))?;
+        let nav = navigator(nav)?;
+        let route = strings(route, route_len, "route")?;
+        let atoms = match (kind, project) {
+            (SavanConsequences::Brave, false) => nav.brave_consequences(route.iter()),
+            (SavanConsequences::Brave, true) => nav.brave_consequences_projecting(route.iter()),
+            (SavanConsequences::Cautious, false) => nav.cautious_consequences(route.iter()),
+            (SavanConsequences::Cautious, true) => {
+                nav.cautious_consequences_projecting(route.iter())
+            }
+            (SavanConsequences::FacetInducing, false) => nav
+                .facet_inducing_atoms(route.iter())
+                .map(|xs| xs.into_iter().collect()),
+            (SavanConsequences::FacetInducing, true) => nav
+                .facet_inducing_atoms_projecting(route.iter())
+                .map(|xs| xs.into_iter().collect()),
+        }
+        .ok_or(NavigatorError::None)?;
+        put(out, c_atoms(atoms))
+    })
+}
+
+/// Computes count of **weight** under **route**.
+///
+/// **weight** is a [SavanWeight](SavanWeight) value; others fail with
+/// `SAVAN_STATUS_INVALID_INPUT`.
+///
+/// # Safety
+/// **nav** must be a valid navigator, **route** an array of **route_len** C strings, and **out**
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn savan_count(
+    nav: *mut SavanNavigator,
+    route: *const *const c_char,
+    route_len: usize,
+    weight: u32,
+    project: bool,
+    out: *mut usize,
+) -> SavanStatus {
+    guard(|| {
+        let weight = SavanWeight::try_from(weight).map_err(|w| invalid("weight", w))?;
+        let mut w = Weight::from(weight);
+        let nav = navigator(nav)?;
+        let route = strings(route, route_len, "route")?;
+        let count = match project {
+            true => weights::count_projecting(&mut w, nav, route.iter()),
+            _ => weights::count(&mut w, nav, route.iter()),
+        }
+        .ok_or(NavigatorError::None)?;
+        match out.as_mut() {
+            Some(out) => *out = count,
+            None => return Err(null("out")),
+        }
+
+        Ok(())
+    })
+}
+
+/// Computes weights of facets under **route** w.r.t. **weight**.
+///
+/// **weight** is a [SavanWeight](SavanWeight) value; others fail with
+/// `SAVAN_STATUS_INVALID_INPUT`.
+///
+/// # Safety
+/// **nav** must be a valid navigator, and **route** an array of **route_len** C strings.
+#[no_mangle]
+pub unsafe extern "C" fn savan_facet_weights(
+    nav: *mut SavanNavigator,
+    route: *const *const c_char,
+    route_len: usize,
+    weight: u32,
+    project: bool,
+    out: *mut *mut SavanWeights,
+) -> SavanStatus {
+    guard(|| {
+        let weight = SavanWeight::try_from(weight).map_err(|w| invalid("weight", w))?;
+        let mut w = Weight::from(weight);
+        let nav = navigator(nav)?;
+        let route = strings(route, route_len, "route")?;
+        let ws = match project {
+            true => facet_weights_projecting(&mut w, nav, &route),
+            _ => facet_weights(&mut w, nav, &route),
+        }
+        .ok_or(NavigatorError::None)?;
+        let (facets, weights): (Vec<_>, Vec<_>) = ws
+            .into_iter()
+            .filter_map(|(f, w)| CString::new(f).ok().map(|f| (f, w)))
+            .unzip();
+        put(out, SavanWeights { facets, weights })
+    })
+}
+
+/// Enumerates up to **limit** models under **route**; all models, if **limit** is zero.
+///
+/// # Safety
+/// **nav** must be a valid navigator, and **route** an array of **route_len** C strings.
+#[no_mangle]
+pub unsafe extern "C" fn savan_models(
+    nav: *mut SavanNavigator,
+    route: *const *const c_char,
+    route_len: usize,
+    limit: usize,
+    out: *mut *mut SavanModels,
+) -> SavanStatus {
+    guard(|| {
+        let nav = navigator(nav)?;
+        let route = strings(route, route_len, "route")?;
+        let limit = (limit > 0).then_some(limit);
+        let models = nav
+            .solutions(limit, route.iter())?
+            .into_iter()
+            .map(c_atoms)
+            .collect();
+        put(out, SavanModels { models })
+    })
+}
+
+/// Returns number of strings in **xs**.
+///
+/// # Safety
+/// **xs** must be a valid list or null.
+#[no_mangle]
+pub unsafe extern "C" fn savan_strings_len(xs: *const SavanStrings) -> usize {
+    xs.as_ref().map_or(0, |xs| xs.items.len())
+}
+
+/// Returns string at **i** in **xs**, or null if out of range; valid as long as **xs**.
+///
+/// # Safety
+/// **xs** must be a valid list or null.
+#[no_mangle]
+pub unsafe extern "C" fn savan_strings_get(xs: *const SavanStrings, i: usize) -> *const c_char {
+    xs.as_ref()
+        .and_then(|xs| xs.items.get(i))
+        .map_or(std::ptr::null(), |x| x.as_ptr())
+}
+
+/// Releases **xs**; null is ignored.
+///
+/// # Safety
+/// **xs** must stem from this library, and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn savan_strings_free(xs: *mut SavanStrings) {
+    if !xs.is_null() {
+        drop(Box::from_raw(xs));
+    }
+}
+
+/// Returns number of facets in **ws**.
+///
+/// # Safety
+/// **ws** must be a valid list or null.
+#[no_mangle]
+pub unsafe extern "C" fn savan_weights_len(ws: *const SavanWeights) -> usize {
+    ws.as_ref().map_or(0, |ws| ws.facets.len())
+}
+
+/// Returns facet at **i** in **ws**, or null if out of range; valid as long as **ws**.
+///
+/// # Safety
+/// **ws** must be a valid list or null.
+#[no_mangle]
+pub unsafe extern "C" fn savan_weights_facet(ws: *const SavanWeights, i: usize) -> *const c_char {
+    ws.as_ref()
+        .and_then(|ws| ws.facets.get(i))
+        .map_or(std::ptr::null(), |x| x.as_ptr())
+}
+
+/// Returns weight of facet at **i** in **ws**, or zero if out of range.
+///
+/// # Safety
+/// **ws** must be a valid list or null.
+#[no_mangle]
+pub unsafe extern "C" fn savan_weights_weight(ws: *const SavanWeights, i: usize) -> usize {
+    ws.as_ref()
+        .and_then(|ws| ws.weights.get(i).copied())
+        .unwrap_or(0)
+}
+
+/// Releases **ws**; null is ignored.
+///
+/// # Safety
+/// **ws** must stem from this library, and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn savan_weights_free(ws: *mut SavanWeights) {
+    if !ws.is_null() {
+        drop(Box::from_raw(ws));
+    }
+}
+
+/// Returns number of models in **ms**.
+///
+/// # Safety
+/// **ms** must be a valid list or null.
+#[no_mangle]
+pub unsafe extern "C" fn savan_models_len(ms: *const SavanModels) -> usize {
+    ms.as_ref().map_or(0, |ms| ms.models.len())
+}
+
+/// Returns atoms of model at **i** in **ms**, or null if out of range; valid as long as **ms**.
+///
+/// # Safety
+/// **ms** must be a valid list or null.
+#[no_mangle]
+pub unsafe extern "C" fn savan_models_get(ms: *const SavanModels, i: usize) -> *const SavanStrings {
+    ms.as_ref()
+        .and_then(|ms| ms.models.get(i))
+        .map_or(std::ptr::null(), |m| m as *const _)
+}
+
+/// Releases **ms**; null is ignored.
+///
+/// # Safety
+/// **ms** must stem from this library, and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn savan_models_free(ms: *mut SavanModels) {
+    if !ms.is_null() {
+        drop(Box::from_raw(ms));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c(xs: &[&str]) -> Vec<CString> {
+        xs.iter().map(|x| CString::new(*x).unwrap()).collect()
+    }
+
+    fn ptrs(xs: &[CString]) -> Vec<*const c_char> {
+        xs.iter().map(|x| x.as_ptr()).collect()
+    }
+
+    unsafe fn read(xs: *const SavanStrings) -> Vec<String> {
+        (0..savan_strings_len(xs))
+            .map(|i| {
+                CStr::from_ptr(savan_strings_get(xs, i))
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn navigate_via_c_api() {
+        unsafe {
+            let program = CString::new("a;b. c;d :- b. e.").unwrap();
+            let args = c(&["0"]);
+            let mut nav = std::ptr::null_mut();
+            let status =
+                savan_navigator_new(program.as_ptr(), ptrs(&args).as_ptr(), args.len(), &mut nav);
+            assert_eq!(status, SavanStatus::Ok);
+
+            let route = c(&["~a"]);
+            let mut atoms = std::ptr::null_mut();
+            let status = savan_consequences(
+                nav,
+                ptrs(&route).as_ptr(),
+                route.len(),
+                SavanConsequences::FacetInducing as u32,
+                false,
+                &mut atoms,
+            );
+            assert_eq!(status, SavanStatus::Ok);
+            assert_eq!(read(atoms), vec!["c", "d"]);
+            savan_strings_free(atoms);
+
+            let mut count = 0;
+            let status = savan_count(
+                nav,
+                std::ptr::null(),
+                0,
+                SavanWeight::AnswerSetCounting as u32,
+                false,
+                &mut count,
+            );
+            assert_eq!(status, SavanStatus::Ok);
+            assert_eq!(count, 3);
+
+            let route = c(&["b"]);
+            let mut ws = std::ptr::null_mut();
+            let status = savan_facet_weights(
+                nav,
+                ptrs(&route).as_ptr(),
+                route.len(),
+                SavanWeight::AnswerSetCounting as u32,
+                false,
+                &mut ws,
+            );
+            assert_eq!(status, SavanStatus::Ok);
+            assert_eq!(savan_weights_len(ws), 4);
+            assert_eq!(
+                CStr::from_ptr(savan_weights_facet(ws, 1)).to_str(),
+                Ok("~c")
+            );
+            assert_eq!(savan_weights_weight(ws, 1), 1);
+            savan_weights_free(ws);
+
+            let mut ms = std::ptr::null_mut();
+            let status = savan_models(nav, ptrs(&route).as_ptr(), route.len(), 0, &mut ms);
+            assert_eq!(status, SavanStatus::Ok);
+            assert_eq!(savan_models_len(ms), 2);
+            assert_eq!(read(savan_models_get(ms, 0)).len(), 3);
+            assert!(savan_models_get(ms, 2).is_null());
+            savan_models_free(ms);
+
+            let route = c(&["a", "b"]);
+            let mut atoms = std::ptr::null_mut();
+            let status = savan_consequences(
+                nav,
+                ptrs(&route).as_ptr(),
+                route.len(),
+                SavanConsequences::Brave as u32,
+                false,
+                &mut atoms,
+            );
+            assert_eq!(status, SavanStatus::Ok);
+            assert_eq!(savan_strings_len(atoms), 0);
+            savan_strings_free(atoms);
+
+            let mut count = 0;
+            let status = savan_count(
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                0,
+                SavanWeight::FacetCounting as u32,
+                false,
+                &mut count,
+            );
+            assert_eq!(status, SavanStatus::NullPointer);
+
+            savan_navigator_free(nav);
+        }
+    }
+
+    #[test]
+    fn report_errors() {
+        unsafe {
+            let program = CString::new("a :- .").unwrap();
+            let mut nav = std::ptr::null_mut();
+            let status = savan_navigator_new(program.as_ptr(), std::ptr::null(), 0, &mut nav);
+            assert_eq!(status, SavanStatus::Clingo);
+            assert!(nav.is_null());
+            assert!(!savan_last_error().is_null());
+
+            let status = savan_navigator_new(std::ptr::null(), std::ptr::null(), 0, &mut nav);
+            assert_eq!(status, SavanStatus::NullPointer);
+            assert_eq!(
+                CStr::from_ptr(savan_last_error()).to_str(),
+                Ok("program is null")
+            );
+
+            let program = CString::new("a;b.").unwrap();
+            let status = savan_navigator_new(program.as_ptr(), std::ptr::null(), 0, &mut nav);
+            assert_eq!(status, SavanStatus::Ok);
+            let mut atoms = std::ptr::null_mut();
+            let status = savan_consequences(nav, std::ptr::null(), 0, 3, false, &mut atoms);
+            assert_eq!(status, SavanStatus::InvalidInput);
+            assert!(atoms.is_null());
+            let mut count = 0;
+            let status = savan_count(nav, std::ptr::null(), 0, u32::MAX, false, &mut count);
+            assert_eq!(status, SavanStatus::InvalidInput);
+            assert_eq!(
+                CStr::from_ptr(savan_last_error()).to_str(),
+                Ok("weight 4294967295 is out of range")
+            );
+            savan_navigator_free(nav);
+        }
+    }
+}
@@ -1,16 +1,358 @@
-use clingo::Symbol;
+mod parser;
+
+use clingo::{Symbol, SymbolType};
+use parser::{Parser, Term};
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+
+/// Error raised on invalid input, pointing at the byte offset **position** in **input**.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("parsing {input:?} failed at position {position}: {message}")]
+pub struct ParseError {
+    pub input: String,
+    pub position: usize,
+    pub message: String,
+}
+
+/// A signed atom, i.e., a facet `a` or `~a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Facet {
+    pub symbol: Symbol,
+    pub positive: bool,
+}
+impl fmt::Display for Facet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.positive {
+            write!(f, "~")?;
+        }
+        write_symbol(f, self.symbol, Quoting::Escaped)
+    }
+}
+
+/// An atom possibly containing variables, e.g., `p(X, 3)` or `q(_, X, X)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern(Term);
+impl Pattern {
+    /// Returns signature `(name, arity)` of atoms matching the pattern, where the name of
+    /// classically negated atoms is prefixed by `-`.
+    pub fn signature(&self) -> Option<(String, usize)> {
+        match &self.0 {
+            Term::Function {
+                name,
+                arguments,
+                positive,
+            } => match positive {
+                true => Some((name.clone(), arguments.len())),
+                _ => Some((format!("-{name}"), arguments.len())),
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks whether **symbol** is an instance of the pattern.
+    ///
+    /// Repeated named variables have to be bound to the same term.
+    pub fn matches(&self, symbol: Symbol) -> bool {
+        self.0.matches(symbol, &mut HashMap::new())
+    }
+}
+
+/// How string arguments of symbols are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Quoting {
+    /// Quoted and escaped as in clingo, e.g., `p("a\"b")`. Output parses back to the same symbol.
+    #[default]
+    Escaped,
+    /// Neither quoted nor escaped, e.g., `p(a"b)`. Intended for display only.
+    Plain,
+}
+
+/// Parses **exp** as ground term.
+pub fn parse(exp: &str) -> Result<Symbol, ParseError> {
+    let mut parser = Parser::new(exp);
+    let term = parser.term()?;
+    parser.expect_end()?;
+
+    term.to_symbol()
+        .map_err(|e| parser.error(0, format!("{e:?}")))
+}
+
+/// Parses **exp** as facet, i.e., an atom optionally preceded by `~`.
+pub fn parse_facet(exp: &str) -> Result<Facet, ParseError> {
+    let mut parser = Parser::new(exp);
+    let facet = facet(&mut parser)?;
+    parser.expect_end()?;
+
+    Ok(facet)
+}
+
+/// Parses **exp** as comma separated list of facets, e.g., `a, ~b(1,"x")`.
+pub fn parse_facets(exp: &str) -> Result<Vec<Facet>, ParseError> {
+    let mut parser = Parser::new(exp);
+    let mut facets = vec![];
+    if parser.at_end() {
+        return Ok(facets);
+    }
+
+    loop {
+        facets.push(facet(&mut parser)?);
+        if !parser.eat(',') {
+            parser.expect_end()?;
+            return Ok(facets);
+        }
+    }
+}
+
+fn facet(parser: &mut Parser) -> Result<Facet, ParseError> {
+    let positive = !parser.eat('~');
+    parser.skip_whitespace();
+    let start = parser.position();
+    let term = parser.term()?;
+    if !term.is_atom() {
+        return Err(parser.error(start, "expected atom"));
+    }
+    let symbol = term
+        .to_symbol()
+        .map_err(|e| parser.error(start, format!("{e:?}")))?;
+
+    Ok(Facet { symbol, positive })
+}
+
+/// Parses **exp** as pattern, i.e., an atom possibly containing variables.
+pub fn parse_pattern(exp: &str) -> Result<Pattern, ParseError> {
+    let mut parser = Parser::with_variables(exp);
+    parser.skip_whitespace();
+    let start = parser.position();
+    let term = parser.term()?;
+    if !term.is_atom() {
+        return Err(parser.error(start, "expected atom"));
+    }
+    parser.expect_end()?;
+
+    Ok(Pattern(term))
+}
+
+/// Returns signature `(name, arity)` of **symbol**, where the name of classically negated atoms
+/// is prefixed by `-`, and tuples have the empty name.
+///
+/// Returns [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if
+/// **symbol** is not a function.
+pub fn signature(symbol: Symbol) -> Option<(String, usize)> {
+    let name = symbol.name().ok()?;
+    let arity = symbol.arguments().ok()?.len();
+    match symbol.is_positive().ok()? {
+        true => Some((name.to_owned(), arity)),
+        _ => Some((format!("-{name}"), arity)),
+    }
+}
 
 /// Converts facet to [String](https://doc.rust-lang.org/std/string/struct.String.html).
 pub fn repr(symbol: Symbol) -> String {
-    symbol.to_string()
+    repr_with(symbol, Quoting::Escaped)
+}
+
+/// Converts **symbol** to [String](https://doc.rust-lang.org/std/string/struct.String.html),
+/// printing strings as specified by **quoting**.
+pub fn repr_with(symbol: Symbol, quoting: Quoting) -> String {
+    let mut out = String::new();
+    match write_symbol(&mut out, symbol, quoting) {
+        Ok(()) => out,
+        Err(_) => symbol.to_string(),
+    }
+}
+
+/// Converts **facets** to a comma separated list, printing strings as specified by **quoting**.
+pub fn repr_facets(facets: &[Facet], quoting: Quoting) -> String {
+    facets
+        .iter()
+        .map(|f| match f.positive {
+            true => repr_with(f.symbol, quoting),
+            _ => format!("~{}", repr_with(f.symbol, quoting)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-pub(crate) fn parse(exp: &str) -> Option<Symbol> {
-    match clingo::parse_term(exp) {
-        Ok(s) => Some(s),
-        Err(e) => {
-            println!("parsing {:?} failed with {:?}", exp, e);
-            None
+fn write_symbol(out: &mut impl fmt::Write, symbol: Symbol, quoting: Quoting) -> fmt::Result {
+    match symbol.symbol_type().map_err(|_| fmt::Error)? {
+        SymbolType::Number => write!(out, "{}", symbol.number().map_err(|_| fmt::Error)?),
+        SymbolType::String => {
+            let s = symbol.string().map_err(|_| fmt::Error)?;
+            match quoting {
+                Quoting::Escaped => write!(
+                    out,
+                    "\"{}\"",
+                    s.replace('\\', "\\\\")
+                        .replace('\n', "\\n")
+                        .replace('"', "\\\"")
+                ),
+                Quoting::Plain => write!(out, "{s}"),
+            }
+        }
+        SymbolType::Function => {
+            let name = symbol.name().map_err(|_| fmt::Error)?;
+            let arguments = symbol.arguments().map_err(|_| fmt::Error)?;
+            if symbol.is_negative().map_err(|_| fmt::Error)? {
+                write!(out, "-")?;
+            }
+            write!(out, "{name}")?;
+            if !arguments.is_empty() || name.is_empty() {
+                write!(out, "(")?;
+                if let Some((first, rest)) = arguments.split_first() {
+                    write_symbol(out, *first, quoting)?;
+                    for argument in rest {
+                        write!(out, ",")?;
+                        write_symbol(out, *argument, quoting)?;
+                    }
+                }
+                if name.is_empty() && arguments.len() == 1 {
+                    write!(out, ",")?;
+                }
+                write!(out, ")")?;
+            }
+
+            Ok(())
+        }
+        SymbolType::Supremum => write!(out, "#sup"),
+        SymbolType::Infimum => write!(out, "#inf"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_terms() -> Result<(), ParseError> {
+        assert_eq!(parse("a")?, Symbol::create_id("a", true).unwrap());
+        assert_eq!(parse(" -a ")?, Symbol::create_id("a", false).unwrap());
+        assert_eq!(parse("-3")?, Symbol::create_number(-3));
+        assert_eq!(
+            parse("p(1, \"x\\\"y\", (a,), #sup)")?,
+            Symbol::create_function(
+                "p",
+                &[
+                    Symbol::create_number(1),
+                    Symbol::create_string("x\"y").unwrap(),
+                    Symbol::create_function("", &[Symbol::create_id("a", true).unwrap()], true)
+                        .unwrap(),
+                    Symbol::create_supremum(),
+                ],
+                true
+            )
+            .unwrap()
+        );
+        assert_eq!(parse("(1)")?, Symbol::create_number(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse("p(1,").map_err(|e| e.position), Err(4));
+        assert_eq!(parse("p(1) q").map_err(|e| e.position), Err(5));
+        assert_eq!(parse("P").map_err(|e| e.position), Err(0));
+        assert_eq!(parse("p(\"a)").map_err(|e| e.position), Err(2));
+        assert_eq!(parse("p(1,)").map_err(|e| e.position), Err(4));
+        assert_eq!(parse("99999999999").map_err(|e| e.position), Err(0));
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn parse_facet_lists() -> Result<(), ParseError> {
+        let fs = parse_facets("a, ~b(1,\"x\")")?;
+        assert_eq!(fs.len(), 2);
+        assert_eq!(fs[0], parse_facet("a")?);
+        assert!(!fs[1].positive);
+        assert_eq!(fs[1].symbol, parse("b(1,\"x\")")?);
+        assert_eq!(repr_facets(&fs, Quoting::Escaped), "a, ~b(1,\"x\")");
+        assert_eq!(repr_facets(&fs, Quoting::Plain), "a, ~b(1,x)");
+
+        assert!(parse_facets(" ")?.is_empty());
+        assert_eq!(parse_facets("a, ~3").map_err(|e| e.position), Err(4));
+        assert_eq!(parse_facets("a,").map_err(|e| e.position), Err(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn patterns() -> Result<(), ParseError> {
+        let pattern = parse_pattern("p(X, 3)")?;
+        assert_eq!(pattern.signature(), Some(("p".to_owned(), 2)));
+        assert!(pattern.matches(parse("p(a,3)")?));
+        assert!(pattern.matches(parse("p(\"x\",3)")?));
+        assert!(!pattern.matches(parse("p(a,4)")?));
+        assert!(!pattern.matches(parse("-p(a,3)")?));
+        assert!(!pattern.matches(parse("q(a,3)")?));
+
+        let pattern = parse_pattern("q(_, X, X)")?;
+        assert!(pattern.matches(parse("q(1,a,a)")?));
+        assert!(!pattern.matches(parse("q(1,a,b)")?));
+
+        assert_eq!(
+            parse_pattern("-r(_)")?.signature(),
+            Some(("-r".to_owned(), 1))
+        );
+        assert_eq!(parse_pattern("X").map_err(|e| e.position), Err(0));
+        assert_eq!(parse("p(X)").map_err(|e| e.position), Err(2));
+
+        Ok(())
+    }
+
+    fn arb_term() -> impl Strategy<Value = Term> {
+        let name = "_?[a-z][a-zA-Z0-9_']{0,4}";
+        let leaf = prop_oneof![
+            any::<i32>().prop_map(Term::Number),
+            "[ -~\n]{0,6}".prop_map(Term::String),
+            (name, any::<bool>()).prop_map(|(name, positive)| Term::Function {
+                name,
+                arguments: vec![],
+                positive
+            }),
+            Just(Term::Supremum),
+            Just(Term::Infimum),
+        ];
+        leaf.prop_recursive(3, 16, 4, move |inner| {
+            prop_oneof![
+                (
+                    name,
+                    prop::collection::vec(inner.clone(), 1..4),
+                    any::<bool>()
+                )
+                    .prop_map(|(name, arguments, positive)| Term::Function {
+                        name,
+                        arguments,
+                        positive
+                    }),
+                prop::collection::vec(inner, 0..4).prop_map(|arguments| Term::Function {
+                    name: String::new(),
+                    arguments,
+                    positive: true
+                }),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(term in arb_term()) {
+            let symbol = term.to_symbol().unwrap();
+            let s = repr(symbol);
+            prop_assert_eq!(&s, &symbol.to_string());
+            prop_assert_eq!(parse(&s), Ok(symbol));
+        }
+
+        #[test]
+        fn round_trip_facets(terms in prop::collection::vec((arb_term(), any::<bool>()), 0..4)) {
+            let facets = terms
+                .iter()
+                .filter(|(t, _)| t.is_atom())
+                .map(|(t, positive)| Facet { symbol: t.to_symbol().unwrap(), positive: *positive })
+                .collect::<Vec<_>>();
+            let s = repr_facets(&facets, Quoting::Escaped);
+            prop_assert_eq!(parse_facets(&s), Ok(facets));
         }
     }
 }
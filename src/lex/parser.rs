@@ -0,0 +1,322 @@
+use super::ParseError;
+use clingo::{ClingoError, Symbol, SymbolType};
+use std::collections::HashMap;
+
+/// Term as read from input, prior to its construction as [Symbol](clingo::Symbol).
+///
+/// Variables only occur in patterns, where `None` denotes the anonymous variable `_`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Term {
+    Number(i32),
+    String(String),
+    Function {
+        name: String,
+        arguments: Vec<Term>,
+        positive: bool,
+    },
+    Supremum,
+    Infimum,
+    Variable(Option<String>),
+}
+impl Term {
+    pub(crate) fn is_atom(&self) -> bool {
+        matches!(self, Self::Function { .. })
+    }
+
+    pub(crate) fn to_symbol(&self) -> Result<Symbol, ClingoError> {
+        match self {
+            Self::Number(n) => Ok(Symbol::create_number(*n)),
+            Self::String(s) => Symbol::create_string(s),
+            Self::Function {
+                name,
+                arguments,
+                positive,
+            } => {
+                let arguments = arguments
+                    .iter()
+                    .map(|t| t.to_symbol())
+                    .collect::<Result<Vec<_>, _>>()?;
+                Symbol::create_function(name, &arguments, *positive)
+            }
+            Self::Supremum => Ok(Symbol::create_supremum()),
+            Self::Infimum => Ok(Symbol::create_infimum()),
+            Self::Variable(_) => Err(ClingoError::FFIError {
+                msg: "cannot construct symbol from non-ground term",
+            }),
+        }
+    }
+
+    /// Checks whether **symbol** is an instance of term, binding variables in **bindings**.
+    pub(crate) fn matches(&self, symbol: Symbol, bindings: &mut HashMap<String, Symbol>) -> bool {
+        match (self, symbol.symbol_type()) {
+            (Self::Variable(None), _) => true,
+            (Self::Variable(Some(v)), _) => *bindings.entry(v.clone()).or_insert(symbol) == symbol,
+            (Self::Number(n), Ok(SymbolType::Number)) => symbol.number().is_ok_and(|m| m == *n),
+            (Self::String(s), Ok(SymbolType::String)) => symbol.string().is_ok_and(|t| t == s),
+            (
+                Self::Function {
+                    name,
+                    arguments,
+                    positive,
+                },
+                Ok(SymbolType::Function),
+            ) => {
+                symbol.name().is_ok_and(|n| n == name)
+                    && symbol.is_positive().is_ok_and(|p| p == *positive)
+                    && symbol.arguments().is_ok_and(|xs| {
+                        xs.len() == arguments.len()
+                            && arguments
+                                .iter()
+                                .zip(xs)
+                                .all(|(t, x)| t.matches(x, bindings))
+                    })
+            }
+            (Self::Supremum, Ok(SymbolType::Supremum)) => true,
+            (Self::Infimum, Ok(SymbolType::Infimum)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Recursive descent parser for ground terms in clingo syntax.
+///
+/// Positions are byte offsets into the complete input.
+pub(crate) struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    variables: bool,
+}
+impl<'a> Parser<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            variables: false,
+        }
+    }
+
+    /// Constructs parser that accepts variables within terms.
+    pub(crate) fn with_variables(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            variables: true,
+        }
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn error(&self, position: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            input: self.input.to_owned(),
+            position,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Consumes **c** after optional whitespace, if it is next.
+    pub(crate) fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.peek() == Some(c) {
+            true => {
+                self.bump();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.pos == self.input.len()
+    }
+
+    pub(crate) fn expect_end(&mut self) -> Result<(), ParseError> {
+        match self.at_end() {
+            true => Ok(()),
+            _ => Err(self.unexpected("end of input")),
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        match self.eat(c) {
+            true => Ok(()),
+            _ => Err(self.unexpected(&format!("{c:?}"))),
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(self.pos, format!("expected {expected}, found {c:?}")),
+            None => self.error(self.pos, format!("expected {expected}, found end of input")),
+        }
+    }
+
+    pub(crate) fn term(&mut self) -> Result<Term, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('-') => {
+                let start = self.pos;
+                self.bump();
+                self.skip_whitespace();
+                if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    return self.number(true).map(Term::Number);
+                }
+                match self.term()? {
+                    Term::Number(n) => n
+                        .checked_neg()
+                        .map(Term::Number)
+                        .ok_or_else(|| self.error(start, "number out of range")),
+                    Term::Function {
+                        name,
+                        arguments,
+                        positive,
+                    } if !name.is_empty() => Ok(Term::Function {
+                        name,
+                        arguments,
+                        positive: !positive,
+                    }),
+                    _ => Err(self.error(start, "classical negation of non-function term")),
+                }
+            }
+            Some('#') => {
+                let start = self.pos;
+                self.bump();
+                match self.word().as_str() {
+                    "sup" | "supremum" => Ok(Term::Supremum),
+                    "inf" | "infimum" => Ok(Term::Infimum),
+                    _ => Err(self.error(start, "expected #sup or #inf")),
+                }
+            }
+            Some('"') => self.string().map(Term::String),
+            Some('(') => {
+                self.bump();
+                let (arguments, trailing_comma) = self.arguments()?;
+                match arguments.len() == 1 && !trailing_comma {
+                    true => Ok(arguments.into_iter().next().expect("one argument")),
+                    _ => Ok(Term::Function {
+                        name: String::new(),
+                        arguments,
+                        positive: true,
+                    }),
+                }
+            }
+            Some(c) if c.is_ascii_digit() => self.number(false).map(Term::Number),
+            Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+                let start = self.pos;
+                let name = self.word();
+                let unprefixed = name.trim_start_matches('_');
+                if unprefixed.is_empty() || unprefixed.starts_with(|c: char| c.is_ascii_uppercase())
+                {
+                    return match self.variables {
+                        true if unprefixed.is_empty() => Ok(Term::Variable(None)),
+                        true => Ok(Term::Variable(Some(name))),
+                        _ => Err(self.error(start, format!("unexpected variable {name:?}"))),
+                    };
+                }
+                if !unprefixed.starts_with(|c: char| c.is_ascii_lowercase()) {
+                    return Err(self.error(start, format!("invalid identifier {name:?}")));
+                }
+                let arguments = match self.peek() == Some('(') {
+                    true => {
+                        self.bump();
+                        let (arguments, trailing_comma) = self.arguments()?;
+                        if trailing_comma {
+                            return Err(self.error(self.pos - 1, "trailing comma in arguments"));
+                        }
+                        arguments
+                    }
+                    _ => vec![],
+                };
+                Ok(Term::Function {
+                    name,
+                    arguments,
+                    positive: true,
+                })
+            }
+            _ => Err(self.unexpected("term")),
+        }
+    }
+
+    /// Parses comma separated terms up to and including the closing parenthesis.
+    ///
+    /// Returns the terms, and whether the last term was followed by a comma.
+    fn arguments(&mut self) -> Result<(Vec<Term>, bool), ParseError> {
+        let mut arguments = vec![];
+        let mut trailing_comma = false;
+        while !self.eat(')') {
+            arguments.push(self.term()?);
+            trailing_comma = self.eat(',');
+            if !trailing_comma {
+                self.expect(')')?;
+                break;
+            }
+        }
+
+        Ok((arguments, trailing_comma))
+    }
+
+    fn word(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '\'')
+        {
+            self.bump();
+        }
+
+        self.input[start..self.pos].to_owned()
+    }
+
+    fn number(&mut self, negative: bool) -> Result<i32, ParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+
+        let digits = &self.input[start..self.pos];
+        match negative {
+            true => format!("-{digits}").parse::<i32>(),
+            _ => digits.parse::<i32>(),
+        }
+        .map_err(|_| self.error(start, "number out of range"))
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('\\') => s.push('\\'),
+                    Some('"') => s.push('"'),
+                    Some('n') => s.push('\n'),
+                    _ => return Err(self.error(self.pos - 1, "invalid escape sequence")),
+                },
+                Some('\0') => return Err(self.error(self.pos - 1, "nul byte in string")),
+                Some(c) => s.push(c),
+                None => return Err(self.error(start, "unterminated string")),
+            }
+        }
+    }
+}
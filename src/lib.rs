@@ -1,4 +1,11 @@
 #![deny(clippy::all)]
 
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "clingo")]
 pub mod lex;
 pub mod nav;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "server")]
+pub mod server;
@@ -0,0 +1,382 @@
+use super::{weakly_dominates, Interrupt, Mode, Optimization, SolverBackend};
+use crate::lex;
+use crate::nav::errors::{NavigatorError, Result};
+use ::clingo::{Control, Model, Part, ShowType, SolveMode, SolveResult, SolverLiteral, Symbol};
+use std::time::Duration;
+
+/// Interval in which running searches check for requested interruption.
+const POLL: Duration = Duration::from_millis(10);
+
+/// [SolverBackend](SolverBackend) backed by clingo.
+///
+/// Failing solve calls keep the control object, except if clingo cannot start solving or close
+/// the solve handle, as its bindings drop the control object then; later calls fail with
+/// [NoControl](NavigatorError::NoControl).
+pub struct Clingo {
+    ctl: Option<Control>,
+    interrupt: Interrupt,
+    optimization: Optimization,
+}
+impl Clingo {
+    /// Constructs backend using clingo configured by **args**.
+    pub fn new(args: Vec<String>) -> Result<Self> {
+        Ok(Self {
+            ctl: Some(::clingo::control(args)?),
+            interrupt: Interrupt::default(),
+            optimization: Optimization::default(),
+        })
+    }
+
+    /// Solves under **assumptions** in **mode**, with optimization mode **opt_mode**, if given,
+    /// and passes each model to **on_model**, until it returns `false`.
+    ///
+    /// The configuration is restored afterwards, also if solving fails.
+    fn search(
+        &mut self,
+        assumptions: &[SolverLiteral],
+        mode: Mode,
+        projecting: bool,
+        opt_mode: Option<String>,
+        mut on_model: impl FnMut(&Model) -> Result<bool>,
+    ) -> Result<()> {
+        let mut settings = vec![];
+        match mode {
+            Mode::Models => (),
+            Mode::Brave => settings.push(("solve.enum_mode", "brave".to_owned())),
+            Mode::Cautious => settings.push(("solve.enum_mode", "cautious".to_owned())),
+        }
+        if projecting {
+            settings.push(("solve.project", "show".to_owned()));
+        }
+        if let Some(value) = opt_mode {
+            settings.push(("solve.opt_mode", value));
+        }
+
+        let mut ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
+        let mut previous = vec![];
+        let mut outcome = Ok(());
+        for (key, value) in &settings {
+            match set(&mut ctl, key, value) {
+                Ok(value) => previous.push((*key, value)),
+                Err(e) => {
+                    outcome = Err(e);
+                    break;
+                }
+            }
+        }
+
+        if outcome.is_ok() {
+            self.interrupt.reset();
+            let mut handle = ctl.solve(SolveMode::YIELD | SolveMode::ASYNC, assumptions)?;
+            loop {
+                while !handle.wait(POLL) && !self.interrupt.is_requested() {}
+                if self.interrupt.is_requested() {
+                    outcome = handle
+                        .cancel()
+                        .map_err(NavigatorError::from)
+                        .and(Err(NavigatorError::Interrupted));
+                    break;
+                }
+                match handle.model() {
+                    Ok(Some(model)) => match on_model(model) {
+                        Ok(true) => (),
+                        Ok(false) => break,
+                        Err(e) => {
+                            outcome = Err(e);
+                            break;
+                        }
+                    },
+                    Ok(None) => break,
+                    Err(e) => {
+                        outcome = Err(e.into());
+                        break;
+                    }
+                }
+                if let Err(e) = handle.resume() {
+                    outcome = Err(e.into());
+                    break;
+                }
+            }
+            ctl = handle.close()?;
+        }
+
+        for (key, value) in previous.iter().rev() {
+            if let Err(e) = set(&mut ctl, key, value) {
+                outcome = outcome.and(Err(e));
+            }
+        }
+        self.ctl = Some(ctl);
+
+        outcome
+    }
+
+    /// Returns optimization mode restricting models according to the current
+    /// [Optimization](Optimization), if any.
+    fn opt_mode(&self) -> Option<String> {
+        match &self.optimization {
+            Optimization::Any => None,
+            Optimization::Optimal => Some("optN".to_owned()),
+            Optimization::Within(bound) => Some(
+                ["enum".to_owned()]
+                    .into_iter()
+                    .chain(bound.iter().map(|c| c.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+        }
+    }
+
+    /// Returns brave consequences over all atoms, and over shown symbols.
+    pub(crate) fn brave_atoms(&mut self) -> Result<(Vec<Symbol>, Vec<Symbol>)> {
+        let mut atoms = vec![];
+        let mut shown = vec![];
+        let opt_mode = self.opt_mode();
+        self.search(&[], Mode::Brave, false, opt_mode, |model| {
+            atoms = model.symbols(ShowType::ATOMS)?;
+            shown = model.symbols(ShowType::SHOWN)?;
+            Ok(true)
+        })?;
+
+        Ok((atoms, shown))
+    }
+}
+
+impl SolverBackend for Clingo {
+    type Atom = Symbol;
+    type Literal = SolverLiteral;
+
+    fn ground(&mut self, program: &str) -> Result<()> {
+        let ctl = self.ctl.as_mut().ok_or(NavigatorError::NoControl)?;
+        ctl.add("base", &[], program)?;
+        ctl.ground(&[Part::new("base", vec![])?])?;
+
+        Ok(())
+    }
+
+    fn atoms(&self) -> Result<Vec<(Symbol, SolverLiteral)>> {
+        let ctl = self.ctl.as_ref().ok_or(NavigatorError::NoControl)?;
+        let mut atoms = vec![];
+        for atom in ctl.symbolic_atoms()?.iter()? {
+            atoms.push((atom.symbol()?, atom.literal()?));
+        }
+
+        Ok(atoms)
+    }
+
+    fn parse_atom(&self, atom: &str) -> Option<Symbol> {
+        lex::parse(atom).ok()
+    }
+
+    fn negate(&self, literal: SolverLiteral) -> SolverLiteral {
+        literal.negate()
+    }
+
+    fn solve(
+        &mut self,
+        assumptions: &[SolverLiteral],
+        mode: Mode,
+        projecting: bool,
+        mut on_model: impl FnMut(&[Symbol]) -> bool,
+    ) -> Result<()> {
+        let opt_mode = self.opt_mode();
+        // while proving optimality, only optimal models are solutions
+        let only_proven = mode == Mode::Models && self.optimization == Optimization::Optimal;
+
+        self.search(assumptions, mode, projecting, opt_mode, |model| {
+            if only_proven && !model.optimality_proven()? {
+                return Ok(true);
+            }
+            Ok(on_model(&model.symbols(ShowType::SHOWN)?))
+        })
+    }
+
+    fn interrupt_handle(&self) -> Interrupt {
+        self.interrupt.clone()
+    }
+
+    fn optimize(&mut self, optimization: Optimization) -> Result<()> {
+        self.optimization = optimization;
+
+        Ok(())
+    }
+
+    fn optimum(&mut self, assumptions: &[SolverLiteral]) -> Result<Option<Vec<i64>>> {
+        let mut cost = None;
+        self.search(
+            assumptions,
+            Mode::Models,
+            false,
+            Some("opt".to_owned()),
+            |model| {
+                let current = model.cost()?;
+                // without optimization statements, any model is optimal
+                let done = current.is_empty();
+                cost = Some(current);
+                Ok(!done)
+            },
+        )?;
+
+        Ok(cost)
+    }
+
+    fn solve_with_costs(
+        &mut self,
+        assumptions: &[SolverLiteral],
+        mut on_model: impl FnMut(&[Symbol], &[i64]) -> bool,
+    ) -> Result<()> {
+        self.search(
+            assumptions,
+            Mode::Models,
+            false,
+            Some("enum".to_owned()),
+            |model| Ok(on_model(&model.symbols(ShowType::SHOWN)?, &model.cost()?)),
+        )
+    }
+
+    /// Clingo offers no bounds per priority, so dominance is checked per model, while the least
+    /// undominated cost found so far bounds the search lexicographically, restarting it on each
+    /// improvement.
+    fn solve_undominated(
+        &mut self,
+        assumptions: &[SolverLiteral],
+        bounds: &[Vec<i64>],
+        mut on_model: impl FnMut(&[Symbol], &[i64]) -> bool,
+    ) -> Result<()> {
+        let mut least: Option<Vec<i64>> = None;
+        let mut models = vec![];
+        loop {
+            let opt_mode = ["enum".to_owned()]
+                .into_iter()
+                .chain(least.iter().flatten().map(|c| c.to_string()))
+                .collect::<Vec<_>>()
+                .join(",");
+            // the bound is inclusive, so a restarted search finds models of least cost again
+            models.clear();
+            let mut improved = false;
+            self.search(assumptions, Mode::Models, false, Some(opt_mode), |model| {
+                let cost = model.cost()?;
+                if bounds.iter().any(|bound| weakly_dominates(bound, &cost)) {
+                    return Ok(true);
+                }
+                // within the bound, any other cost is less
+                if least.as_ref() == Some(&cost) {
+                    models.push(model.symbols(ShowType::SHOWN)?);
+                    return Ok(true);
+                }
+                least = Some(cost);
+                improved = true;
+                Ok(false)
+            })?;
+            if !improved {
+                break;
+            }
+        }
+
+        if let Some(cost) = least {
+            for atoms in models {
+                if !on_model(&atoms, &cost) {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the core clingo found for **assumptions**.
+    fn core(&mut self, assumptions: &[SolverLiteral]) -> Result<Option<Vec<SolverLiteral>>> {
+        let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
+
+        self.interrupt.reset();
+        let mut handle = ctl.solve(SolveMode::ASYNC, assumptions)?;
+        while !handle.wait(POLL) && !self.interrupt.is_requested() {}
+        let outcome = match self.interrupt.is_requested() {
+            true => handle
+                .cancel()
+                .map_err(NavigatorError::from)
+                .and(Err(NavigatorError::Interrupted)),
+            _ => match handle.get() {
+                Ok(result) if result.contains(SolveResult::UNSATISFIABLE) => {
+                    handle.core().map(Some).map_err(NavigatorError::from)
+                }
+                Ok(_) => Ok(None),
+                Err(e) => Err(e.into()),
+            },
+        };
+
+        self.ctl = Some(handle.close()?);
+
+        outcome
+    }
+}
+
+/// Sets configuration entry **key** to **value**, and returns its previous value.
+fn set(ctl: &mut Control, key: &str, value: &str) -> Result<String> {
+    let c = ctl.configuration_mut()?;
+    let sk = c.root().and_then(|rk| c.map_at(rk, key))?;
+    let previous = c.value_get(sk)?;
+    c.value_set(sk, value)?;
+
+    Ok(previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_control_on_errors() -> Result<()> {
+        let mut backend = Clingo::new(vec!["0".to_owned()])?;
+        backend.ground("a;b. :~ a. [1@1]")?;
+
+        let failed = backend.search(&[], Mode::Models, true, None, |_| Err(NavigatorError::None));
+        assert!(matches!(failed, Err(NavigatorError::None)));
+        let failed = backend.search(&[], Mode::Brave, false, Some("x".to_owned()), |_| Ok(true));
+        assert!(failed.is_err());
+
+        let mut n = 0;
+        backend.solve(&[], Mode::Models, false, |_| {
+            n += 1;
+            true
+        })?;
+        assert_eq!(n, 2);
+
+        backend.optimize(Optimization::Optimal)?;
+        let (_, shown) = backend.brave_atoms()?;
+        assert_eq!(shown, vec![lex::parse("b")?]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn solve_undominated_once_per_model() -> Result<()> {
+        let mut backend = Clingo::new(vec!["0".to_owned()])?;
+        backend.ground("1 {a;b;c} 1. :~ c. [1@1]")?;
+
+        let mut models = vec![];
+        backend.solve_undominated(&[], &[], |atoms, cost| {
+            models.push((
+                atoms.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+                cost.to_vec(),
+            ));
+            true
+        })?;
+        models.sort();
+        assert_eq!(
+            models,
+            vec![
+                (vec!["a".to_owned()], vec![0]),
+                (vec!["b".to_owned()], vec![0])
+            ]
+        );
+
+        let mut n = 0;
+        backend.solve_undominated(&[], &[vec![0]], |_, _| {
+            n += 1;
+            true
+        })?;
+        assert_eq!(n, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,427 @@
+use super::{Interrupt, Mode, Optimization, SolverBackend};
+use crate::nav::errors::{NavigatorError, Result};
+use std::collections::{BTreeSet, HashMap};
+
+/// [SolverBackend](SolverBackend) over an explicit list of answer sets.
+///
+/// Literals are indices of atoms paired with their sign. All atoms are shown, so projecting has
+/// no effect. Useful for testing navigation without grounding and solving a program.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    atoms: Vec<String>,
+    answer_sets: Vec<BTreeSet<usize>>,
+    costs: Vec<Vec<i64>>,
+    optimization: Optimization,
+    interrupt: Interrupt,
+}
+impl Memory {
+    /// Constructs backend whose solutions are **answer_sets**, given as lists of atoms.
+    ///
+    /// Duplicate answer sets are dropped.
+    pub fn new<A: ToString>(answer_sets: impl IntoIterator<Item = Vec<A>>) -> Self {
+        Self::with_costs(answer_sets.into_iter().map(|xs| (xs, vec![])))
+    }
+
+    /// Constructs backend whose solutions are **answer_sets**, given as lists of atoms paired
+    /// with their cost, in order of decreasing priority.
+    ///
+    /// Duplicate answer sets are dropped, keeping the first cost given.
+    pub fn with_costs<A: ToString>(
+        answer_sets: impl IntoIterator<Item = (Vec<A>, Vec<i64>)>,
+    ) -> Self {
+        let (answer_sets, costs): (Vec<BTreeSet<String>>, Vec<Vec<i64>>) = answer_sets
+            .into_iter()
+            .map(|(xs, cost)| (xs.iter().map(|x| normalize(&x.to_string())).collect(), cost))
+            .unzip();
+        let atoms = answer_sets
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let index = atoms
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a.clone(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut unique = vec![];
+        let mut unique_costs = vec![];
+        for (xs, cost) in answer_sets.into_iter().zip(costs) {
+            let xs = xs.iter().map(|x| index[x]).collect::<BTreeSet<_>>();
+            if !unique.contains(&xs) {
+                unique.push(xs);
+                unique_costs.push(cost);
+            }
+        }
+
+        Self {
+            atoms,
+            answer_sets: unique,
+            costs: unique_costs,
+            optimization: Optimization::default(),
+            interrupt: Interrupt::default(),
+        }
+    }
+
+    /// Returns indices of answer sets satisfying **assumptions**, regardless of their cost.
+    fn models(&self, assumptions: &[(usize, bool)]) -> Vec<usize> {
+        (0..self.answer_sets.len())
+            .filter(|m| {
+                assumptions
+                    .iter()
+                    .all(|(i, p)| self.answer_sets[*m].contains(i) == *p)
+            })
+            .collect()
+    }
+
+    fn atoms_of(&self, xs: &BTreeSet<usize>) -> Vec<String> {
+        xs.iter().map(|i| self.atoms[*i].clone()).collect()
+    }
+}
+
+impl SolverBackend for Memory {
+    type Atom = String;
+    type Literal = (usize, bool);
+
+    /// Accepts only empty programs, since answer sets are given explicitly.
+    fn ground(&mut self, program: &str) -> Result<()> {
+        match program.trim().is_empty() {
+            true => Ok(()),
+            _ => Err(NavigatorError::InvalidInput(
+                "in-memory backend cannot ground programs".to_owned(),
+            )),
+        }
+    }
+
+    fn atoms(&self) -> Result<Vec<(String, (usize, bool))>> {
+        Ok(self
+            .atoms
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a.clone(), (i, true)))
+            .collect())
+    }
+
+    fn parse_atom(&self, atom: &str) -> Option<String> {
+        let atom = normalize(atom);
+        match atom.is_empty() {
+            true => None,
+            _ => Some(atom),
+        }
+    }
+
+    fn negate(&self, (i, positive): (usize, bool)) -> (usize, bool) {
+        (i, !positive)
+    }
+
+    fn solve(
+        &mut self,
+        assumptions: &[(usize, bool)],
+        mode: Mode,
+        _projecting: bool,
+        mut on_model: impl FnMut(&[String]) -> bool,
+    ) -> Result<()> {
+        self.interrupt.reset();
+        let mut models = self.models(assumptions);
+        match &self.optimization {
+            Optimization::Any => (),
+            Optimization::Optimal => {
+                if let Some(optimum) = models.iter().map(|m| &self.costs[*m]).min().cloned() {
+                    models.retain(|m| self.costs[*m] == optimum);
+                }
+            }
+            Optimization::Within(bound) => models.retain(|m| self.costs[*m] <= *bound),
+        }
+        let mut models = models.iter().map(|m| &self.answer_sets[*m]);
+
+        match mode {
+            Mode::Models => {
+                for xs in models {
+                    if self.interrupt.is_requested() {
+                        return Err(NavigatorError::Interrupted);
+                    }
+                    if !on_model(&self.atoms_of(xs)) {
+                        break;
+                    }
+                }
+            }
+            Mode::Brave | Mode::Cautious => {
+                if let Some(first) = models.next() {
+                    let consequences = models.fold(first.clone(), |acc, xs| match mode {
+                        Mode::Brave => acc.union(xs).copied().collect(),
+                        _ => acc.intersection(xs).copied().collect(),
+                    });
+                    if self.interrupt.is_requested() {
+                        return Err(NavigatorError::Interrupted);
+                    }
+                    on_model(&self.atoms_of(&consequences));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn interrupt_handle(&self) -> Interrupt {
+        self.interrupt.clone()
+    }
+
+    fn optimize(&mut self, optimization: Optimization) -> Result<()> {
+        self.optimization = optimization;
+
+        Ok(())
+    }
+
+    fn solve_with_costs(
+        &mut self,
+        assumptions: &[(usize, bool)],
+        mut on_model: impl FnMut(&[String], &[i64]) -> bool,
+    ) -> Result<()> {
+        self.interrupt.reset();
+        for m in self.models(assumptions) {
+            if self.interrupt.is_requested() {
+                return Err(NavigatorError::Interrupted);
+            }
+            if !on_model(&self.atoms_of(&self.answer_sets[m]), &self.costs[m]) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn optimum(&mut self, assumptions: &[(usize, bool)]) -> Result<Option<Vec<i64>>> {
+        Ok(self
+            .models(assumptions)
+            .into_iter()
+            .map(|m| self.costs[m].clone())
+            .min())
+    }
+}
+
+/// Removes whitespace outside of string constants from **atom**.
+fn normalize(atom: &str) -> String {
+    let mut out = String::new();
+    let (mut quoted, mut escaped) = (false, false);
+    for c in atom.chars() {
+        match (quoted, escaped, c) {
+            (true, false, '\\') => escaped = true,
+            (true, false, '"') | (false, _, '"') => quoted = !quoted,
+            (true, true, _) => escaped = false,
+            (false, _, c) if c.is_whitespace() => continue,
+            _ => (),
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+/// Fixtures shared by tests of navigation over [Memory](Memory).
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::Memory;
+    use crate::nav::errors::Result;
+    use crate::nav::Navigator;
+
+    /// Answer sets of `a;b. c;d :- b. e.`.
+    pub(crate) fn toy() -> Result<Navigator<Memory>> {
+        let answer_sets = vec![vec!["a", "e"], vec!["b", "c", "e"], vec!["b", "d", "e"]];
+        Navigator::with_backend(Memory::new(answer_sets), "")
+    }
+
+    pub(crate) fn strings(xs: &[&str]) -> Vec<String> {
+        xs.iter().map(|x| x.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{strings, toy};
+    use super::*;
+    use crate::nav::errors::NavigatorError;
+    use crate::nav::facets::Facets;
+    use crate::nav::soe::Collect;
+    use crate::nav::weights::{count, facet_weights, Weight};
+    use crate::nav::Navigator;
+
+    #[test]
+    fn navigate_explicit_answer_sets() -> Result<()> {
+        let mut nav = toy()?;
+
+        assert_eq!(nav.enumerate_solutions_quietly(None, ["~c"].iter())?, 2);
+        assert_eq!(nav.solutions(Some(1), ["b", " d "].iter())?.len(), 1);
+        assert!(nav.solutions(None, ["a", "b"].iter())?.is_empty());
+        assert_eq!(
+            nav.solutions_after(1, Some(5), ["b"].iter())?,
+            nav.solutions(None, ["b"].iter())?[1..].to_vec()
+        );
+        assert_eq!(nav.is_known("c".to_owned()), Some(true));
+        assert_eq!(nav.is_known("f".to_owned()), Some(false));
+
+        let mut fs = nav
+            .facet_inducing_atoms(["~c"].iter())
+            .ok_or(NavigatorError::None)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        fs.sort();
+        assert_eq!(fs, vec!["a", "b", "d"]);
+        assert_eq!(
+            nav.cautious_consequences(["b"].iter()),
+            Some(vec!["b".to_owned(), "e".to_owned()])
+        );
+        assert_eq!(nav.brave_consequences(["a", "b"].iter()), Some(vec![]));
+
+        assert_eq!(
+            count(
+                &mut Weight::FacetCounting,
+                &mut nav,
+                std::iter::empty::<String>()
+            ),
+            Some(8)
+        );
+        let ws = facet_weights(&mut Weight::AnswerSetCounting, &mut nav, &["b".to_owned()])
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(ws.len(), 4);
+        assert!(ws.iter().all(|(_, w)| *w == 1));
+
+        let targets = ["a".to_owned(), "c".to_owned(), "d".to_owned()];
+        let xs = nav
+            .sieve_quiet(["b"].iter(), &targets, false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(xs.len(), 2);
+
+        assert!(Navigator::with_backend(Memory::new(vec![vec!["a"]]), "a.").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_nearest_solutions() -> Result<()> {
+        let mut nav = toy()?;
+
+        assert_eq!(
+            nav.nearest_solutions(None, ["a", "~e", "f"].iter())?,
+            vec![(strings(&["a", "e"]), strings(&["~e"]))]
+        );
+        let nearest = nav.nearest_solutions(None, ["a", "c", "d"].iter())?;
+        assert_eq!(nearest.len(), 3);
+        assert!(nearest.iter().all(|(_, violated)| violated.len() == 2));
+        assert_eq!(
+            nav.nearest_solutions(Some(1), ["b"].iter())?,
+            vec![(strings(&["b", "c", "e"]), vec![])]
+        );
+
+        let atoms = (0..30).map(|i| format!("x{i}")).collect::<Vec<_>>();
+        let mut nav = Navigator::with_backend(Memory::new(atoms.iter().map(|a| vec![a])), "")?;
+        let nearest = nav.nearest_solutions(None, atoms.iter())?;
+        assert_eq!(nearest.len(), 30);
+        assert!(nearest.iter().all(|(_, violated)| violated.len() == 29));
+
+        Ok(())
+    }
+
+    #[test]
+    fn interrupt_solving() -> Result<()> {
+        let mut backend = Memory::new(vec![vec!["a"], vec!["b"], vec!["c"]]);
+        let interrupt = backend.interrupt_handle();
+
+        let mut n = 0;
+        let result = backend.solve(&[], Mode::Models, false, |_| {
+            n += 1;
+            interrupt.interrupt();
+            true
+        });
+        assert!(matches!(result, Err(NavigatorError::Interrupted)));
+        assert_eq!(n, 1);
+
+        let mut n = 0;
+        backend.solve(&[], Mode::Models, false, |_| {
+            n += 1;
+            true
+        })?;
+        assert_eq!(n, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn navigate_optimal_answer_sets() -> Result<()> {
+        // answer sets of `a;b. c;d :- b. e. :~ a. [2@1] :~ c. [1@1] :~ d. [1@2]`
+        let answer_sets = vec![
+            (vec!["a", "e"], vec![0, 2]),
+            (vec!["b", "c", "e"], vec![0, 1]),
+            (vec!["b", "d", "e"], vec![1, 0]),
+        ];
+        let mut nav = Navigator::with_backend(Memory::with_costs(answer_sets), "")?;
+        let none = std::iter::empty::<String>;
+
+        nav.optimize(Optimization::Optimal)?;
+        assert_eq!(nav.optimum(none())?, Some(vec![0, 1]));
+        assert_eq!(nav.optimum(["a"].iter())?, Some(vec![0, 2]));
+        assert_eq!(nav.optimum(["a", "b"].iter())?, None);
+        assert_eq!(nav.enumerate_solutions_quietly(None, none())?, 1);
+        assert_eq!(
+            nav.cautious_consequences(none()),
+            Some(vec!["b".to_owned(), "c".to_owned(), "e".to_owned()])
+        );
+        assert_eq!(nav.facet_inducing_atoms(none()).map(|fs| fs.len()), Some(0));
+        assert_eq!(
+            nav.solutions(None, ["~c"].iter())?,
+            vec![vec!["a".to_owned(), "e".to_owned()]]
+        );
+
+        nav.optimize(Optimization::Within(vec![0, 2]))?;
+        assert_eq!(nav.enumerate_solutions_quietly(None, none())?, 2);
+        let mut fs = nav
+            .facet_inducing_atoms(none())
+            .ok_or(NavigatorError::None)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        fs.sort();
+        assert_eq!(fs, vec!["a", "b", "c"]);
+
+        nav.optimize(Optimization::Any)?;
+        assert_eq!(nav.enumerate_solutions_quietly(None, none())?, 3);
+        assert_eq!(toy()?.optimum(none())?, Some(vec![]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn solve_undominated_models() -> Result<()> {
+        let mut backend = Memory::with_costs(vec![
+            (vec!["a"], vec![0, 2]),
+            (vec!["b"], vec![0, 1]),
+            (vec!["c"], vec![1, 0]),
+            (vec!["d"], vec![1, 0]),
+        ]);
+
+        let mut models = vec![];
+        backend.solve_undominated(&[], &[vec![0, 1]], |atoms, cost| {
+            models.push((atoms.join(" "), cost.to_vec()));
+            true
+        })?;
+        assert_eq!(
+            models,
+            vec![("c".to_owned(), vec![1, 0]), ("d".to_owned(), vec![1, 0])]
+        );
+
+        let mut n = 0;
+        backend.solve_undominated(&[], &[vec![0, 1], vec![1, 0]], |_, _| {
+            n += 1;
+            true
+        })?;
+        assert_eq!(n, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_atoms() {
+        assert_eq!(normalize(" p(1, \"a b\") "), "p(1,\"a b\")");
+        assert_eq!(normalize("q(\"\\\" x\", y)"), "q(\"\\\" x\",y)");
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(feature = "clingo")]
+mod clingo;
+mod memory;
+
+#[cfg(feature = "clingo")]
+pub use self::clingo::Clingo;
+#[cfg(test)]
+pub(crate) use memory::fixtures;
+pub use memory::Memory;
+
+use super::errors::{NavigatorError, Result};
+use std::fmt;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Reasoning mode of a solve call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Enumerate solutions.
+    Models,
+    /// Enumerate approximations of the brave consequences; the last model is exact.
+    Brave,
+    /// Enumerate approximations of the cautious consequences; the last model is exact.
+    Cautious,
+}
+
+/// Models of programs with optimization statements that count as solutions.
+///
+/// Costs are given in order of decreasing priority and compared lexicographically, like clingo
+/// reports them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Optimization {
+    /// All models, regardless of their cost.
+    #[default]
+    Any,
+    /// Optimal models only.
+    Optimal,
+    /// Models whose cost is at most the given bound, e.g., near-optimal ones.
+    Within(Vec<i64>),
+}
+
+/// Handle to interrupt solve calls of a backend, possibly from another thread.
+///
+/// Interrupted solve calls fail with
+/// [Interrupted](super::errors::NavigatorError::Interrupted); requests made while no solve call
+/// is running are discarded by the next one.
+#[derive(Debug, Clone, Default)]
+pub struct Interrupt(Arc<AtomicBool>);
+impl Interrupt {
+    /// Requests interruption of the running solve call.
+    pub fn interrupt(&self) {
+        self.0.store(true, Ordering::SeqCst)
+    }
+
+    /// Checks whether interruption was requested.
+    pub fn is_requested(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Discards requested interruption.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::SeqCst)
+    }
+}
+
+/// Solver functionalities navigation builds upon.
+///
+/// [Navigator](super::Navigator) as well as [Facets](super::facets::Facets),
+/// [weights](super::weights) and [Collect](super::soe::Collect) only rely on this trait, so
+/// solvers other than clingo, e.g., ones running within a browser, can be plugged in.
+pub trait SolverBackend {
+    /// Atom of the ground program.
+    type Atom: Clone + Eq + Hash + fmt::Display;
+    /// Solver literal of an atom.
+    type Literal: Copy + Eq;
+
+    /// Adds and grounds **program**.
+    fn ground(&mut self, program: &str) -> Result<()>;
+    /// Returns atoms of the ground program together with their literals.
+    fn atoms(&self) -> Result<Vec<(Self::Atom, Self::Literal)>>;
+    /// Parses **atom**, if it is a syntactically valid atom.
+    fn parse_atom(&self, atom: &str) -> Option<Self::Atom>;
+    /// Returns negation of **literal**.
+    fn negate(&self, literal: Self::Literal) -> Self::Literal;
+    /// Solves under **assumptions** in **mode**, while projecting onto shown atoms, if
+    /// **projecting** is set.
+    ///
+    /// Passes shown atoms of each model to **on_model**, until it returns `false`. Solver
+    /// settings are restored afterwards.
+    fn solve(
+        &mut self,
+        assumptions: &[Self::Literal],
+        mode: Mode,
+        projecting: bool,
+        on_model: impl FnMut(&[Self::Atom]) -> bool,
+    ) -> Result<()>;
+    /// Returns handle to interrupt solve calls.
+    fn interrupt_handle(&self) -> Interrupt;
+    /// Restricts solutions of subsequent solve calls according to **optimization**.
+    ///
+    /// Backends without support for optimization accept [Any](Optimization::Any) only.
+    fn optimize(&mut self, optimization: Optimization) -> Result<()> {
+        match optimization {
+            Optimization::Any => Ok(()),
+            _ => Err(NavigatorError::InvalidInput(
+                "backend does not support optimization".to_owned(),
+            )),
+        }
+    }
+    /// Returns cost of optimal models under **assumptions**, regardless of the current
+    /// [Optimization](Optimization), or
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if there are
+    /// no models under **assumptions**.
+    ///
+    /// The cost is empty for programs without optimization statements, which is what backends
+    /// without support for optimization report for any program.
+    fn optimum(&mut self, assumptions: &[Self::Literal]) -> Result<Option<Vec<i64>>> {
+        let mut satisfiable = false;
+        self.solve(assumptions, Mode::Models, false, |_| {
+            satisfiable = true;
+            false
+        })?;
+
+        Ok(satisfiable.then(Vec::new))
+    }
+    /// Solves under **assumptions**, regardless of the current [Optimization](Optimization).
+    ///
+    /// Passes shown atoms of each model together with its cost, in order of decreasing
+    /// priority, to **on_model**, until it returns `false`. By default, models are those of
+    /// [solve](SolverBackend::solve) with empty costs.
+    fn solve_with_costs(
+        &mut self,
+        assumptions: &[Self::Literal],
+        mut on_model: impl FnMut(&[Self::Atom], &[i64]) -> bool,
+    ) -> Result<()> {
+        self.solve(assumptions, Mode::Models, false, |atoms| {
+            on_model(atoms, &[])
+        })
+    }
+    /// Solves under **assumptions** for models whose cost is not weakly dominated by any cost in
+    /// **bounds**, i.e., is less than each bound at some priority, regardless of the current
+    /// [Optimization](Optimization).
+    ///
+    /// Passes shown atoms of each such model of lexicographically least cost together with the
+    /// cost to **on_model**, until it returns `false`. By default, models of
+    /// [solve_with_costs](SolverBackend::solve_with_costs) are filtered in a single pass.
+    fn solve_undominated(
+        &mut self,
+        assumptions: &[Self::Literal],
+        bounds: &[Vec<i64>],
+        mut on_model: impl FnMut(&[Self::Atom], &[i64]) -> bool,
+    ) -> Result<()> {
+        let mut least: Option<Vec<i64>> = None;
+        let mut models = vec![];
+        self.solve_with_costs(assumptions, |atoms, cost| {
+            if bounds.iter().any(|bound| weakly_dominates(bound, cost)) {
+                return true;
+            }
+            match least.as_deref() {
+                Some(least) if least == cost => models.push(atoms.to_vec()),
+                Some(least) if least < cost => (),
+                _ => {
+                    least = Some(cost.to_vec());
+                    models = vec![atoms.to_vec()];
+                }
+            }
+            true
+        })?;
+
+        if let Some(cost) = least {
+            for atoms in models {
+                if !on_model(&atoms, &cost) {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+    /// Returns a subset of **assumptions** without solutions, or
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if there are
+    /// solutions under **assumptions**.
+    ///
+    /// Cores need not be minimal; by default, all assumptions are returned.
+    fn core(&mut self, assumptions: &[Self::Literal]) -> Result<Option<Vec<Self::Literal>>> {
+        let mut satisfiable = false;
+        self.solve(assumptions, Mode::Models, false, |_| {
+            satisfiable = true;
+            false
+        })?;
+
+        Ok((!satisfiable).then(|| assumptions.to_vec()))
+    }
+}
+
+/// Checks whether cost **x** is at most **y** at every priority.
+pub(crate) fn weakly_dominates(x: &[i64], y: &[i64]) -> bool {
+    x.len() == y.len() && x.iter().zip(y).all(|(a, b)| a <= b)
+}
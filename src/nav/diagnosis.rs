@@ -0,0 +1,370 @@
+use super::backend::SolverBackend;
+use super::errors::{NavigatorError, Result};
+use super::facets::TruthValue;
+use super::Navigator;
+
+/// Why a route has no solutions, and how to repair it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// Minimal subset of the route without solutions, i.e., dropping any of its facets yields
+    /// solutions. Empty, if the program itself has no solutions.
+    pub core: Vec<String>,
+    /// Minimal sets of facets whose removal from the route yields solutions, ordered by size.
+    /// Empty, if the program itself has no solutions.
+    pub corrections: Vec<Vec<String>>,
+}
+
+/// Why an atom is true in all or false in all solutions under a route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// Either [True](TruthValue::True) or [False](TruthValue::False).
+    pub value: TruthValue,
+    /// Minimal subset of the route forcing the value, i.e., dropping any of its facets makes the
+    /// atom undecided. Empty, if the program itself forces the value.
+    pub reasons: Vec<String>,
+}
+
+/// Diagnosing routes without solutions, and explaining consequences of routes.
+pub trait Diagnose {
+    /// Returns [Diagnosis](Diagnosis) of **route**, or
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if there are
+    /// solutions under **route**.
+    ///
+    /// Facets over unknown atoms are ignored. Corrections are found as minimal hitting sets of
+    /// cores, so solve calls grow with the number of corrections and cores, not with the
+    /// number of subsets of the route.
+    fn diagnose<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Option<Diagnosis>>;
+    /// Returns [Explanation](Explanation) of why **atom** is a cautious consequence, or not a
+    /// brave consequence under **route**, or
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if **atom**
+    /// is undecided, or there are no solutions under **route**.
+    ///
+    /// Facets over unknown atoms are ignored; fails on unknown **atom**.
+    fn explain<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        atom: &str,
+    ) -> Result<Option<Explanation>>;
+}
+impl<B: SolverBackend> Diagnose for Navigator<B> {
+    fn diagnose<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Option<Diagnosis>> {
+        let facets = self.facets(route);
+        let all = (0..facets.len()).collect::<Vec<_>>();
+        let core = match core_of(&mut self.backend, &facets, &all, &[])? {
+            Some(core) => shrink(&mut self.backend, &facets, &[], core)?,
+            None => return Ok(None),
+        };
+
+        let corrections = corrections(&mut self.backend, &facets, &[], vec![core.clone()], false)?;
+
+        Ok(Some(Diagnosis {
+            core: names(&facets, core),
+            corrections: corrections.into_iter().map(|c| names(&facets, c)).collect(),
+        }))
+    }
+
+    fn explain<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        atom: &str,
+    ) -> Result<Option<Explanation>> {
+        let atom = atom.trim();
+        let positive = self
+            .expression_to_literal(atom)
+            .filter(|_| !atom.starts_with('~'))
+            .ok_or_else(|| NavigatorError::InvalidInput(format!("unknown atom {atom}")))?;
+        let negative = self.backend.negate(positive);
+
+        let facets = self.facets(route);
+        let all = (0..facets.len()).collect::<Vec<_>>();
+        let forced = core_of(&mut self.backend, &facets, &all, &[negative])?;
+        let excluded = core_of(&mut self.backend, &facets, &all, &[positive])?;
+
+        let (value, probe, core) = match (forced, excluded) {
+            (Some(core), None) => (TruthValue::True, negative, core),
+            (None, Some(core)) => (TruthValue::False, positive, core),
+            _ => return Ok(None),
+        };
+        let reasons = shrink(&mut self.backend, &facets, &[probe], core)?;
+
+        Ok(Some(Explanation {
+            value,
+            reasons: names(&facets, reasons),
+        }))
+    }
+}
+/// Returns minimal sets of indices of **facets** whose removal yields solutions together with
+/// **fixed**, ordered by size, given some **cores** of **facets**, or only those of least size,
+/// if **minimum** is set.
+///
+/// Each correction hits every core, so candidates are the minimal hitting sets of the cores
+/// found so far: a candidate either yields solutions, or the remaining facets have a core it
+/// misses, which rules it out. Empty, if **fixed** has no solutions on its own.
+pub(crate) fn corrections<B: SolverBackend>(
+    backend: &mut B,
+    facets: &[(String, B::Literal)],
+    fixed: &[B::Literal],
+    cores: Vec<Vec<usize>>,
+    minimum: bool,
+) -> Result<Vec<Vec<usize>>> {
+    let mut candidates = vec![vec![]];
+    for core in &cores {
+        if core.is_empty() {
+            return Ok(vec![]);
+        }
+        candidates = hitting(candidates, core);
+    }
+
+    let mut found: Vec<Vec<usize>> = vec![];
+    while let Some(candidate) = candidates
+        .iter()
+        .filter(|c| !found.contains(c))
+        .min_by_key(|c| (c.len(), *c))
+        .cloned()
+    {
+        if minimum && found.first().is_some_and(|c| c.len() < candidate.len()) {
+            break;
+        }
+        let rest = (0..facets.len())
+            .filter(|i| !candidate.contains(i))
+            .collect::<Vec<_>>();
+        match core_of(backend, facets, &rest, fixed)? {
+            Some(core) => {
+                let core = shrink(backend, facets, fixed, core)?;
+                if core.is_empty() {
+                    return Ok(vec![]);
+                }
+                candidates = hitting(candidates, &core);
+            }
+            None => found.push(candidate),
+        }
+    }
+    found.sort_by(|x, y| (x.len(), x).cmp(&(y.len(), y)));
+
+    Ok(found)
+}
+
+/// Returns minimal hitting sets of cores and **core**, given minimal hitting sets **sets** of
+/// the cores.
+fn hitting(sets: Vec<Vec<usize>>, core: &[usize]) -> Vec<Vec<usize>> {
+    let (mut hitting, missing): (Vec<_>, Vec<_>) = sets
+        .into_iter()
+        .partition(|set| set.iter().any(|i| core.contains(i)));
+
+    let mut extended = missing
+        .iter()
+        .flat_map(|set| {
+            core.iter().map(move |i| {
+                let mut set = set.clone();
+                set.push(*i);
+                set.sort();
+                set
+            })
+        })
+        .collect::<Vec<_>>();
+    extended.sort_by(|x, y| (x.len(), x).cmp(&(y.len(), y)));
+    extended.dedup();
+    for set in extended {
+        if !hitting.iter().any(|h| h.iter().all(|i| set.contains(i))) {
+            hitting.push(set);
+        }
+    }
+
+    hitting
+}
+
+/// Returns indices of a subset of **facets** at **indices** without solutions together with
+/// **fixed**, or [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if
+/// there are solutions.
+pub(crate) fn core_of<B: SolverBackend>(
+    backend: &mut B,
+    facets: &[(String, B::Literal)],
+    indices: &[usize],
+    fixed: &[B::Literal],
+) -> Result<Option<Vec<usize>>> {
+    let mut assumptions = indices.iter().map(|i| facets[*i].1).collect::<Vec<_>>();
+    assumptions.extend_from_slice(fixed);
+
+    Ok(backend.core(&assumptions)?.map(|core| {
+        indices
+            .iter()
+            .copied()
+            .filter(|i| core.contains(&facets[*i].1))
+            .collect()
+    }))
+}
+
+/// Shrinks **core**, indices of **facets** without solutions together with **fixed**, to a
+/// minimal such subset by deleting one facet after another, adopting smaller cores found on the
+/// way.
+pub(crate) fn shrink<B: SolverBackend>(
+    backend: &mut B,
+    facets: &[(String, B::Literal)],
+    fixed: &[B::Literal],
+    mut core: Vec<usize>,
+) -> Result<Vec<usize>> {
+    let mut i = 0;
+    while i < core.len() {
+        let mut rest = core.clone();
+        rest.remove(i);
+        match core_of(backend, facets, &rest, fixed)? {
+            Some(smaller) => core = smaller,
+            None => i += 1,
+        }
+    }
+
+    Ok(core)
+}
+
+fn names<L>(facets: &[(String, L)], indices: Vec<usize>) -> Vec<String> {
+    indices.into_iter().map(|i| facets[i].0.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::fixtures::{strings, toy};
+    use crate::nav::backend::Memory;
+
+    #[test]
+    fn diagnose_routes() -> Result<()> {
+        let mut nav = toy()?;
+
+        assert_eq!(nav.diagnose(["a", "~c", "f"].iter())?, None);
+
+        let diagnosis = nav.diagnose(["a", "c", "d", "a"].iter())?;
+        assert_eq!(
+            diagnosis,
+            Some(Diagnosis {
+                core: strings(&["c", "d"]),
+                corrections: vec![
+                    strings(&["a", "c"]),
+                    strings(&["a", "d"]),
+                    strings(&["c", "d"])
+                ],
+            })
+        );
+
+        let diagnosis = nav
+            .diagnose(["b", "~e", "c"].iter())?
+            .ok_or(crate::nav::errors::NavigatorError::None)?;
+        assert_eq!(diagnosis.core, strings(&["~e"]));
+        assert_eq!(diagnosis.corrections, vec![strings(&["~e"])]);
+
+        let mut unsat = Navigator::with_backend(Memory::new(Vec::<Vec<String>>::new()), "")?;
+        assert_eq!(
+            unsat.diagnose(std::iter::empty::<String>())?,
+            Some(Diagnosis {
+                core: vec![],
+                corrections: vec![],
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn diagnose_long_routes() -> Result<()> {
+        let atoms = (0..30).map(|i| format!("x{i}")).collect::<Vec<_>>();
+        let answer_sets = atoms.iter().map(|a| vec![a.as_str(), "y"]);
+        let mut nav = Navigator::with_backend(Memory::new(answer_sets), "")?;
+
+        let mut route = vec!["x0".to_owned(), "x1".to_owned()];
+        route.extend(atoms[2..].iter().map(|a| format!("~{a}")));
+        let diagnosis = nav.diagnose(route.iter())?;
+        assert_eq!(
+            diagnosis,
+            Some(Diagnosis {
+                core: strings(&["x0", "x1"]),
+                corrections: vec![strings(&["x0"]), strings(&["x1"])],
+            })
+        );
+
+        let diagnosis = nav
+            .diagnose(atoms.iter())?
+            .ok_or(crate::nav::errors::NavigatorError::None)?;
+        assert_eq!(diagnosis.core.len(), 2);
+        assert_eq!(diagnosis.corrections.len(), 30);
+        assert!(diagnosis.corrections.iter().all(|c| c.len() == 29));
+        assert_eq!(diagnosis.corrections[0], atoms[..29].to_vec());
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_consequences() -> Result<()> {
+        let mut nav = toy()?;
+
+        let explanation = |value, reasons: &[&str]| {
+            Some(Explanation {
+                value,
+                reasons: strings(reasons),
+            })
+        };
+        assert_eq!(
+            nav.explain(["~a", "~d", "x"].iter(), "c")?,
+            explanation(TruthValue::True, &["~a", "~d"])
+        );
+        assert_eq!(
+            nav.explain(["~a", "~d"].iter(), " d ")?,
+            explanation(TruthValue::False, &["~d"])
+        );
+        assert_eq!(
+            nav.explain(["b"].iter(), "e")?,
+            explanation(TruthValue::True, &[])
+        );
+        assert_eq!(
+            nav.explain(["b"].iter(), "a")?,
+            explanation(TruthValue::False, &["b"])
+        );
+        assert_eq!(nav.explain(["b"].iter(), "c")?, None);
+        assert_eq!(nav.explain(["a", "b"].iter(), "c")?, None);
+        assert!(nav.explain(["b"].iter(), "f").is_err());
+        assert!(nav.explain(["b"].iter(), "~c").is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn explain_consequences_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let explanation = nav
+            .explain(["~d", "~a", "e"].iter(), "c")?
+            .ok_or(crate::nav::errors::NavigatorError::None)?;
+        assert_eq!(explanation.value, TruthValue::True);
+        assert_eq!(explanation.reasons, strings(&["~d", "~a"]));
+
+        let explanation = nav
+            .explain(["~d", "~a", "e"].iter(), "a")?
+            .ok_or(crate::nav::errors::NavigatorError::None)?;
+        assert_eq!(explanation.value, TruthValue::False);
+        assert_eq!(explanation.reasons, strings(&["~a"]));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn diagnose_routes_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let diagnosis = nav
+            .diagnose(["~e", "a", "c"].iter())?
+            .ok_or(crate::nav::errors::NavigatorError::None)?;
+        assert!(diagnosis.core == strings(&["~e"]) || diagnosis.core == strings(&["a", "c"]));
+        assert_eq!(
+            diagnosis.corrections,
+            vec![strings(&["~e", "a"]), strings(&["~e", "c"])]
+        );
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use thiserror::Error;
 #[allow(unused)]
 #[derive(Error, Debug)]
 pub enum NavigatorError {
+    #[cfg(feature = "clingo")]
     #[error("ClingoError: ")]
     Clingo(#[from] clingo::ClingoError),
     #[error("Unwrapped None.")]
@@ -11,8 +12,13 @@ pub enum NavigatorError {
     NoControl,
     #[error("IOError: ")]
     IOError(#[from] std::io::Error),
-    #[error("Invalid input.")]
+    #[error("Interrupted.")]
+    Interrupted,
+    #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[cfg(feature = "clingo")]
+    #[error("ParseError: {0}")]
+    Parse(#[from] crate::lex::ParseError),
 }
 
 pub type Result<T> = std::result::Result<T, NavigatorError>;
@@ -1,223 +1,177 @@
+use super::backend::{Mode, SolverBackend};
 use super::utils::ToHashSet;
 use super::Navigator;
-use clingo::{SolverLiteral, Symbol};
 use std::collections::HashSet;
-
-pub(crate) fn consequences(
-    nav: &mut Navigator,
-    route: &[SolverLiteral],
-    kind: &str,
-) -> Option<Vec<Symbol>> {
-    let mut ctl = nav.ctl.take()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, kind))
-                .ok()
-        })
-        .ok()?;
-
+use std::io::IsTerminal;
+
+const TRUE_BADGE: &str = "\x1b[0;30;42m[T]\x1b[0m";
+const UNDECIDED_BADGE: &str = "\x1b[0;30;44m[U]\x1b[0m";
+const FALSE_BADGE: &str = "\x1b[0;30;41m[F]\x1b[0m";
+
+/// Returns **mode** consequences under **route**, i.e., the last model found in **mode**.
+fn last_model<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    mode: Mode,
+    projecting: bool,
+) -> Option<Vec<B::Atom>> {
     let mut xs = vec![];
-    let mut handle = ctl.solve(clingo::SolveMode::YIELD, route).ok()?;
-
-    while let Ok(Some(ys)) = handle.model() {
-        xs = ys.symbols(clingo::ShowType::SHOWN).ok()?;
-        handle.resume().ok()?;
-    }
-    let mut ctl = handle.close().ok()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, "auto"))
-                .ok()
+    nav.backend
+        .solve(route, mode, projecting, |ys| {
+            xs = ys.to_vec();
+            true
         })
         .ok()?;
-    nav.ctl = Some(ctl);
 
     Some(xs)
 }
 
-pub(crate) fn consequences_projecting(
-    nav: &mut Navigator,
-    route: &[SolverLiteral],
-    kind: &str,
-) -> Option<Vec<Symbol>> {
-    let mut ctl = nav.ctl.take()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, kind))
-                .ok()
-        })
-        .ok()?;
-
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.project"))
-                .map(|sk| c.value_set(sk, "show"))
-                .ok()
-        })
-        .ok()?;
-
-    let mut xs = vec![];
-    let mut handle = ctl.solve(clingo::SolveMode::YIELD, route).ok()?;
-
-    while let Ok(Some(ys)) = handle.model() {
-        xs = ys.symbols(clingo::ShowType::SHOWN).ok()?;
-        handle.resume().ok()?;
-    }
-    let mut ctl = handle.close().ok()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, "auto"))
-                .ok()
-        })
-        .ok()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.project"))
-                .map(|sk| c.value_set(sk, "auto"))
-                .ok()
-        })
-        .ok()?;
-    nav.ctl = Some(ctl);
+pub(crate) fn consequences<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    mode: Mode,
+) -> Option<Vec<B::Atom>> {
+    last_model(nav, route, mode, false)
+}
 
-    Some(xs)
+pub(crate) fn consequences_projecting<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    mode: Mode,
+) -> Option<Vec<B::Atom>> {
+    last_model(nav, route, mode, true)
 }
 
-pub(crate) fn consequences_count(
-    nav: &mut Navigator,
-    route: &[SolverLiteral],
-    kind: &str,
+pub(crate) fn consequences_count<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    mode: Mode,
 ) -> Option<usize> {
-    let mut ctl = nav.ctl.take()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, kind))
-                .ok()
-        })
-        .ok()?;
-
-    let mut count = 0;
-    let mut handle = ctl.solve(clingo::SolveMode::YIELD, route).ok()?;
-
-    while let Ok(Some(ys)) = handle.model() {
-        count = ys.symbols(clingo::ShowType::SHOWN).ok()?.len();
-        handle.resume().ok()?;
-    }
-    let mut ctl = handle.close().ok()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, "auto"))
-                .ok()
-        })
-        .ok()?;
-    nav.ctl = Some(ctl);
-
-    Some(count)
+    last_model(nav, route, mode, false).map(|xs| xs.len())
 }
 
-pub(crate) fn consequences_count_projecting(
-    nav: &mut Navigator,
-    route: &[SolverLiteral],
-    kind: &str,
+pub(crate) fn consequences_count_projecting<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    mode: Mode,
 ) -> Option<usize> {
-    let mut ctl = nav.ctl.take()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, kind))
-                .ok()
-        })
-        .ok()?;
+    last_model(nav, route, mode, true).map(|xs| xs.len())
+}
 
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.project"))
-                .map(|sk| c.value_set(sk, "show"))
-                .ok()
-        })
-        .ok()?;
+/// Truth value of an atom among solutions under a route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TruthValue {
+    /// True in all solutions.
+    True,
+    /// Neither true in all, nor false in all solutions.
+    Undecided,
+    /// False in all solutions.
+    False,
+}
+impl TruthValue {
+    /// Returns badge of truth value, colored via ANSI escape codes, if **color** is set.
+    pub fn badge(&self, color: bool) -> &'static str {
+        match (self, color) {
+            (Self::True, true) => TRUE_BADGE,
+            (Self::Undecided, true) => UNDECIDED_BADGE,
+            (Self::False, true) => FALSE_BADGE,
+            (Self::True, _) => "[T]",
+            (Self::Undecided, _) => "[U]",
+            (Self::False, _) => "[F]",
+        }
+    }
+}
 
+/// Renders **table** of atoms and their truth values, one atom per line.
+///
+/// Badges are colored, if **color** is set.
+pub fn render(table: &[(String, TruthValue)], color: bool) -> String {
+    table
+        .iter()
+        .map(|(atom, v)| format!("{} {atom}\n", v.badge(color)))
+        .collect()
+}
 
-    let mut count = 0;
-    let mut handle = ctl.solve(clingo::SolveMode::YIELD, route).ok()?;
+/// Prints **table** of atoms and their truth values, coloring badges only if standard output is
+/// a terminal and `NO_COLOR` is not set.
+pub fn print_table(table: &[(String, TruthValue)]) {
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    print!("{}", render(table, color));
+}
 
-    while let Ok(Some(ys)) = handle.model() {
-        count = ys.symbols(clingo::ShowType::SHOWN).ok()?.len();
-        handle.resume().ok()?;
-    }
-    let mut ctl = handle.close().ok()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.enum_mode"))
-                .map(|sk| c.value_set(sk, "auto"))
-                .ok()
-        })
-        .ok()?;
-    ctl.configuration_mut()
-        .map(|c| {
-            c.root()
-                .and_then(|rk| c.map_at(rk, "solve.project"))
-                .map(|sk| c.value_set(sk, "auto"))
-                .ok()
+fn truth_values<B: SolverBackend>(
+    backend: &B,
+    atoms: &[String],
+    bcs: &[B::Atom],
+    ccs: &[B::Atom],
+) -> Option<Vec<(String, TruthValue)>> {
+    atoms
+        .iter()
+        .map(|atom| {
+            let atom_ = backend.parse_atom(atom)?;
+            let v = match (ccs.contains(&atom_), bcs.contains(&atom_)) {
+                (true, _) => TruthValue::True,
+                (_, false) => TruthValue::False,
+                _ => TruthValue::Undecided,
+            };
+            Some((atom.clone(), v))
         })
-        .ok()?;
-    nav.ctl = Some(ctl);
-
-    Some(count)
+        .collect()
 }
 
 /// Functionalities revolving around facets of a program.
 pub trait Facets {
+    /// Atom of the ground program.
+    type Atom;
     /// Colors for truth values.
-    const T: &'static str = "\x1b[0;30;42m[T]\x1b[0m";
-    const U: &'static str = "\x1b[0;30;44m[U]\x1b[0m";
-    const F: &'static str = "\x1b[0;30;41m[F]\x1b[0m";
+    const T: &'static str = TRUE_BADGE;
+    const U: &'static str = UNDECIDED_BADGE;
+    const F: &'static str = FALSE_BADGE;
     /// Returns brave consequences found under **route**.
     fn brave_consequences<S: ToString>(
         &mut self,
         route: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>>;
+    ) -> Option<Vec<Self::Atom>>;
     /// Returns brave consequences found under **route**, while projecting onto shown atoms.
     fn brave_consequences_projecting<S: ToString>(
         &mut self,
         route: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>>;
+    ) -> Option<Vec<Self::Atom>>;
     /// Returns cautious consequences found under **route**.
     fn cautious_consequences<S: ToString>(
         &mut self,
         route: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>>;
+    ) -> Option<Vec<Self::Atom>>;
     /// Returns cautious consequences found under **route**, while projecting onto shown atoms.
     fn cautious_consequences_projecting<S: ToString>(
         &mut self,
         route: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>>;
+    ) -> Option<Vec<Self::Atom>>;
     /// Returns facet-inducing atoms found under **route**.
     fn facet_inducing_atoms<S: ToString>(
         &mut self,
         route: impl Iterator<Item = S>,
-    ) -> Option<HashSet<Symbol>>;
+    ) -> Option<HashSet<Self::Atom>>;
     /// Returns facet-inducing atoms found under **route**, while projecting onto shown atoms.
     fn facet_inducing_atoms_projecting<S: ToString>(
         &mut self,
         route: impl Iterator<Item = S>,
-    ) -> Option<HashSet<Symbol>>;
+    ) -> Option<HashSet<Self::Atom>>;
+    /// Returns truth values of **atoms** among solutions under **route**.
+    ///
+    /// Under a route without solutions, all atoms are considered false.
+    fn truth_values<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        atoms: &[String],
+    ) -> Option<Vec<(String, TruthValue)>>;
+    /// Returns truth values of **atoms** among solutions under **route**, while projecting onto
+    /// shown atoms.
+    fn truth_values_projecting<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        atoms: &[String],
+    ) -> Option<Vec<(String, TruthValue)>>;
     /// Prints literals modeled under **route**, and returns facet-inducing atoms under **route**.
     fn learned_that(
         &mut self,
@@ -233,69 +187,56 @@ pub trait Facets {
         write_to: Option<String>,
     ) -> Option<Vec<String>>;
 }
-impl Facets for Navigator {
+impl<B: SolverBackend> Facets for Navigator<B> {
+    type Atom = B::Atom;
+
     fn brave_consequences<S: ToString>(
         &mut self,
         peek_on: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>> {
-        let route = peek_on
-            .map(|s| self.expression_to_literal(s))
-            .flatten()
-            .collect::<Vec<_>>();
-        consequences(self, &route, "brave")
+    ) -> Option<Vec<B::Atom>> {
+        let route = self.route(peek_on);
+        consequences(self, &route, Mode::Brave)
     }
 
     fn brave_consequences_projecting<S: ToString>(
         &mut self,
         peek_on: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>> {
-        let route = peek_on
-            .map(|s| self.expression_to_literal(s))
-            .flatten()
-            .collect::<Vec<_>>();
+    ) -> Option<Vec<B::Atom>> {
+        let route = self.route(peek_on);
 
-        consequences_projecting(self, &route, "brave")
+        consequences_projecting(self, &route, Mode::Brave)
     }
 
     fn cautious_consequences<S: ToString>(
         &mut self,
         peek_on: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>> {
-        let route = peek_on
-            .map(|s| self.expression_to_literal(s))
-            .flatten()
-            .collect::<Vec<_>>();
+    ) -> Option<Vec<B::Atom>> {
+        let route = self.route(peek_on);
 
-        consequences(self, &route, "cautious")
+        consequences(self, &route, Mode::Cautious)
     }
 
     fn cautious_consequences_projecting<S: ToString>(
         &mut self,
         peek_on: impl Iterator<Item = S>,
-    ) -> Option<Vec<Symbol>> {
-        let route = peek_on
-            .map(|s| self.expression_to_literal(s))
-            .flatten()
-            .collect::<Vec<_>>();
+    ) -> Option<Vec<B::Atom>> {
+        let route = self.route(peek_on);
 
-        consequences_projecting(self, &route, "cautious")
+        consequences_projecting(self, &route, Mode::Cautious)
     }
 
     fn facet_inducing_atoms<S: ToString>(
         &mut self,
         peek_on: impl Iterator<Item = S>,
-    ) -> Option<HashSet<Symbol>> {
-        let route = peek_on
-            .map(|s| self.expression_to_literal(s))
-            .flatten()
-            .collect::<Vec<_>>();
+    ) -> Option<HashSet<B::Atom>> {
+        let route = self.route(peek_on);
 
-        let bcs = consequences(self, &route, "brave")?;
+        let bcs = consequences(self, &route, Mode::Brave)?;
 
         match !bcs.is_empty() {
-            true => consequences(self, &route, "cautious")
+            true => consequences(self, &route, Mode::Cautious)
                 .as_ref()
-                .and_then(|ccs| Some(bcs.difference_as_set(ccs))),
+                .map(|ccs| bcs.difference_as_set(ccs)),
             _ => Some(bcs.to_hashset()),
         }
     }
@@ -303,22 +244,45 @@ impl Facets for Navigator {
     fn facet_inducing_atoms_projecting<S: ToString>(
         &mut self,
         peek_on: impl Iterator<Item = S>,
-    ) -> Option<HashSet<Symbol>> {
-        let route = peek_on
-            .map(|s| self.expression_to_literal(s))
-            .flatten()
-            .collect::<Vec<_>>();
+    ) -> Option<HashSet<B::Atom>> {
+        let route = self.route(peek_on);
 
-        let bcs = consequences_projecting(self, &route, "brave")?;
+        let bcs = consequences_projecting(self, &route, Mode::Brave)?;
 
         match !bcs.is_empty() {
-            true => consequences_projecting(self, &route, "cautious")
+            true => consequences_projecting(self, &route, Mode::Cautious)
                 .as_ref()
-                .and_then(|ccs| Some(bcs.difference_as_set(ccs))),
+                .map(|ccs| bcs.difference_as_set(ccs)),
             _ => Some(bcs.to_hashset()),
         }
     }
 
+    fn truth_values<S: ToString>(
+        &mut self,
+        peek_on: impl Iterator<Item = S>,
+        atoms: &[String],
+    ) -> Option<Vec<(String, TruthValue)>> {
+        let route = self.route(peek_on);
+
+        let bcs = consequences(self, &route, Mode::Brave)?;
+        let ccs = consequences(self, &route, Mode::Cautious)?;
+
+        truth_values(&self.backend, atoms, &bcs, &ccs)
+    }
+
+    fn truth_values_projecting<S: ToString>(
+        &mut self,
+        peek_on: impl Iterator<Item = S>,
+        atoms: &[String],
+    ) -> Option<Vec<(String, TruthValue)>> {
+        let route = self.route(peek_on);
+
+        let bcs = consequences_projecting(self, &route, Mode::Brave)?;
+        let ccs = consequences_projecting(self, &route, Mode::Cautious)?;
+
+        truth_values(&self.backend, atoms, &bcs, &ccs)
+    }
+
     fn learned_that(
         &mut self,
         facets: &[String],
@@ -336,12 +300,13 @@ impl Facets for Navigator {
             todo!()
         }
         {
-            facets.into_iter().for_each(|f| match cc.contains(&f) {
+            facets.iter().for_each(|f| match cc.contains(f) {
                 true => println!("{f}"),
-                _ => match !bc.contains(&f) {
-                    true => println!("~{f}"),
-                    _ => (),
-                },
+                _ => {
+                    if !bc.contains(f) {
+                        println!("~{f}")
+                    }
+                }
             });
         }
 
@@ -373,12 +338,13 @@ impl Facets for Navigator {
             todo!()
         }
         {
-            facets.into_iter().for_each(|f| match cc.contains(&f) {
+            facets.iter().for_each(|f| match cc.contains(f) {
                 true => println!("{f}"),
-                _ => match !bc.contains(&f) {
-                    true => println!("~{f}"),
-                    _ => (),
-                },
+                _ => {
+                    if !bc.contains(f) {
+                        println!("~{f}")
+                    }
+                }
             });
         }
 
@@ -394,7 +360,7 @@ impl Facets for Navigator {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "clingo"))]
 mod tests {
     use crate::nav::errors::NavigatorError;
 
@@ -410,17 +376,17 @@ mod tests {
             .brave_consequences(["a"].iter())
             .ok_or(NavigatorError::None)?;
         assert_eq!(bcs.len(), 2);
-        assert!(bcs.contains(&lex::parse("a").ok_or(NavigatorError::None)?));
-        assert!(bcs.contains(&lex::parse("e").ok_or(NavigatorError::None)?));
+        assert!(bcs.contains(&lex::parse("a")?));
+        assert!(bcs.contains(&lex::parse("e")?));
 
         let bcs = nav
             .brave_consequences(["b"].iter())
             .ok_or(NavigatorError::None)?;
         assert_eq!(bcs.len(), 4);
-        assert!(bcs.contains(&lex::parse("b").ok_or(NavigatorError::None)?));
-        assert!(bcs.contains(&lex::parse("c").ok_or(NavigatorError::None)?));
-        assert!(bcs.contains(&lex::parse("d").ok_or(NavigatorError::None)?));
-        assert!(bcs.contains(&lex::parse("e").ok_or(NavigatorError::None)?));
+        assert!(bcs.contains(&lex::parse("b")?));
+        assert!(bcs.contains(&lex::parse("c")?));
+        assert!(bcs.contains(&lex::parse("d")?));
+        assert!(bcs.contains(&lex::parse("e")?));
 
         let bcs = nav
             .brave_consequences(["a", "b"].iter())
@@ -438,15 +404,15 @@ mod tests {
             .cautious_consequences(["a"].iter())
             .ok_or(NavigatorError::None)?;
         assert_eq!(ccs.len(), 2);
-        assert!(ccs.contains(&lex::parse("a").ok_or(NavigatorError::None)?));
-        assert!(ccs.contains(&lex::parse("e").ok_or(NavigatorError::None)?));
+        assert!(ccs.contains(&lex::parse("a")?));
+        assert!(ccs.contains(&lex::parse("e")?));
 
         let ccs = nav
             .cautious_consequences(["b"].iter())
             .ok_or(NavigatorError::None)?;
         assert_eq!(ccs.len(), 2);
-        assert!(ccs.contains(&lex::parse("b").ok_or(NavigatorError::None)?));
-        assert!(ccs.contains(&lex::parse("e").ok_or(NavigatorError::None)?));
+        assert!(ccs.contains(&lex::parse("b")?));
+        assert!(ccs.contains(&lex::parse("e")?));
 
         let ccs = nav
             .cautious_consequences(["a", "b"].iter())
@@ -469,8 +435,8 @@ mod tests {
             .facet_inducing_atoms(["b"].iter())
             .ok_or(NavigatorError::None)?;
         assert_eq!(fs.len(), 2);
-        assert!(fs.contains(&lex::parse("c").ok_or(NavigatorError::None)?));
-        assert!(fs.contains(&lex::parse("d").ok_or(NavigatorError::None)?));
+        assert!(fs.contains(&lex::parse("c")?));
+        assert!(fs.contains(&lex::parse("d")?));
 
         let fs = nav
             .facet_inducing_atoms(["a", "b"].iter())
@@ -481,9 +447,9 @@ mod tests {
             .facet_inducing_atoms(["~c"].iter())
             .ok_or(NavigatorError::None)?;
         assert_eq!(fs.len(), 3);
-        assert!(fs.contains(&lex::parse("a").ok_or(NavigatorError::None)?));
-        assert!(fs.contains(&lex::parse("b").ok_or(NavigatorError::None)?));
-        assert!(fs.contains(&lex::parse("d").ok_or(NavigatorError::None)?));
+        assert!(fs.contains(&lex::parse("a")?));
+        assert!(fs.contains(&lex::parse("b")?));
+        assert!(fs.contains(&lex::parse("d")?));
 
         Ok(())
     }
@@ -494,18 +460,45 @@ mod tests {
 
         let fs = nav
             .learned_that(
-                &vec![
+                &[
                     "a".to_owned(),
                     "b".to_owned(),
                     "c".to_owned(),
                     "d".to_owned(),
                 ],
-                &vec!["a".to_owned()],
+                &["a".to_owned()],
                 None,
             )
             .ok_or(NavigatorError::None)?;
         assert_eq!(fs.len(), 0);
 
+        Ok(())
+    }
+    #[test]
+    fn truth_value_table() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+        let atoms = ["a", "b", "c", "e"].map(|a| a.to_owned());
+
+        let table = nav
+            .truth_values(["b"].iter(), &atoms)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            table.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec![
+                TruthValue::False,
+                TruthValue::True,
+                TruthValue::Undecided,
+                TruthValue::True
+            ]
+        );
+        assert_eq!(
+            render(&table, false),
+            "[F] a\n[T] b\n[U] c\n[T] e\n".to_owned()
+        );
+        assert!(render(&table, true).contains(<Navigator as Facets>::U));
+
+        assert!(nav.truth_values(["b"].iter(), &["a,".to_owned()]).is_none());
+
         Ok(())
     }
 }
@@ -0,0 +1,184 @@
+use super::facets::Facets;
+use super::Navigator;
+use crate::lex;
+use clingo::Symbol;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Returns facet-inducing atoms found under **route** grouped by signature, and, for signatures
+/// in **by**, subsequently by the values of the listed argument positions.
+pub fn facet_groups<S: ToString>(
+    nav: &mut Navigator,
+    route: impl Iterator<Item = S>,
+    by: &HashMap<(String, usize), Vec<usize>>,
+) -> Option<Vec<FacetGroup>> {
+    nav.facet_inducing_atoms(route).map(|fs| group(fs, by))
+}
+
+/// Returns facet-inducing atoms found under **route** grouped by signature, and, for signatures
+/// in **by**, subsequently by the values of the listed argument positions, while projecting onto
+/// shown atoms.
+pub fn facet_groups_projecting<S: ToString>(
+    nav: &mut Navigator,
+    route: impl Iterator<Item = S>,
+    by: &HashMap<(String, usize), Vec<usize>>,
+) -> Option<Vec<FacetGroup>> {
+    nav.facet_inducing_atoms_projecting(route)
+        .map(|fs| group(fs, by))
+}
+
+/// Node of a tree of facet-inducing atoms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetGroup {
+    /// Signature `(name, arity)`, or argument position and value the group is formed by.
+    pub key: Key,
+    /// Number of atoms within the group, including all subgroups.
+    pub count: usize,
+    /// Subgroups ordered by key, if the group is split further.
+    pub children: Vec<FacetGroup>,
+    /// Atoms of the group in symbol order, if it is not split further.
+    pub atoms: Vec<Symbol>,
+}
+
+/// Key of a [FacetGroup](FacetGroup).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    /// Atoms with signature `name/arity`.
+    Signature(String, usize),
+    /// Atoms with **value** at argument **position**.
+    Argument { position: usize, value: Symbol },
+}
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signature(name, arity) => write!(f, "{name}/{arity}"),
+            Self::Argument { value, .. } => write!(f, "{}", lex::repr(*value)),
+        }
+    }
+}
+
+/// Groups **atoms** by signature, and, for signatures in **by**, subsequently by the values of
+/// the listed argument positions.
+///
+/// Argument positions out of range are ignored. Groups are ordered by signature and symbol order
+/// of argument values respectively.
+pub fn group(
+    atoms: impl IntoIterator<Item = Symbol>,
+    by: &HashMap<(String, usize), Vec<usize>>,
+) -> Vec<FacetGroup> {
+    let mut signatures: HashMap<(String, usize), Vec<Symbol>> = HashMap::new();
+    atoms.into_iter().for_each(|atom| {
+        if let Some(sig) = lex::signature(atom) {
+            signatures.entry(sig).or_default().push(atom);
+        }
+    });
+
+    let mut signatures = signatures.into_iter().collect::<Vec<_>>();
+    signatures.sort_by(|(x, _), (y, _)| x.cmp(y));
+
+    signatures
+        .into_iter()
+        .map(|((name, arity), atoms)| {
+            let positions = by
+                .get(&(name.clone(), arity))
+                .map(|ps| {
+                    ps.iter()
+                        .copied()
+                        .filter(|p| *p < arity)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            node(Key::Signature(name, arity), atoms, &positions)
+        })
+        .collect()
+}
+
+fn node(key: Key, mut atoms: Vec<Symbol>, positions: &[usize]) -> FacetGroup {
+    let count = atoms.len();
+    match positions.split_first() {
+        None => {
+            atoms.sort_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal));
+            FacetGroup {
+                key,
+                count,
+                children: vec![],
+                atoms,
+            }
+        }
+        Some((position, rest)) => {
+            let mut values: Vec<(Symbol, Vec<Symbol>)> = vec![];
+            for atom in atoms {
+                let value = match atom
+                    .arguments()
+                    .ok()
+                    .and_then(|xs| xs.get(*position).copied())
+                {
+                    Some(value) => value,
+                    None => continue,
+                };
+                match values.iter_mut().find(|(v, _)| *v == value) {
+                    Some((_, xs)) => xs.push(atom),
+                    None => values.push((value, vec![atom])),
+                }
+            }
+            values.sort_by(|(x, _), (y, _)| x.partial_cmp(y).unwrap_or(Ordering::Equal));
+
+            FacetGroup {
+                key,
+                count,
+                children: values
+                    .into_iter()
+                    .map(|(value, xs)| {
+                        node(
+                            Key::Argument {
+                                position: *position,
+                                value,
+                            },
+                            xs,
+                            rest,
+                        )
+                    })
+                    .collect(),
+                atoms: vec![],
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::errors::Result;
+    use super::*;
+    use crate::nav::errors::NavigatorError;
+
+    #[test]
+    fn group_by_signature_and_argument() -> Result<()> {
+        let mut nav = Navigator::new(
+            "task(1..2). machine(a;b). 1{assign(T,M): machine(M)}1 :- task(T). {idle(M)} :- machine(M).",
+            vec!["0".to_string()],
+        )?;
+        let by = HashMap::from([(("assign".to_owned(), 2), vec![0])]);
+
+        let groups = facet_groups(&mut nav, std::iter::empty::<String>(), &by)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].key, Key::Signature("assign".to_owned(), 2));
+        assert_eq!(groups[0].count, 4);
+        assert_eq!(groups[0].children.len(), 2);
+        assert_eq!(groups[0].children[0].key.to_string(), "1");
+        assert_eq!(groups[0].children[0].count, 2);
+        assert_eq!(groups[0].children[0].atoms.len(), 2);
+        assert_eq!(groups[1].key.to_string(), "idle/1");
+        assert_eq!(groups[1].count, 2);
+        assert!(groups[1].children.is_empty());
+
+        let groups =
+            facet_groups(&mut nav, ["assign(1,a)"].iter(), &by).ok_or(NavigatorError::None)?;
+        assert_eq!(groups[0].count, 2);
+        assert_eq!(groups[0].children.len(), 1);
+        assert_eq!(groups[0].children[0].key.to_string(), "2");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+use super::backend::SolverBackend;
+use super::preview::{brave_and_cautious, preview};
+use super::Navigator;
+use std::collections::{BTreeSet, HashMap};
+
+/// Implications among facets under a route.
+///
+/// Facets are ordered by their atoms, sorted, each atom `a` followed by `~a`, and so are
+/// implications by their source and target.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImplicationGraph {
+    /// Facets under the route, i.e., nodes of the graph.
+    pub facets: Vec<String>,
+    /// Pairs `(f, g)` such that activating `f` forces `g`.
+    pub implications: Vec<(String, String)>,
+    /// Classes of at least two facets implying each other, i.e., having the same solutions.
+    pub equivalent: Vec<Vec<String>>,
+    /// Pairs of atoms not true together in any solution.
+    pub exclusive: Vec<(String, String)>,
+}
+impl ImplicationGraph {
+    /// Returns graph in DOT format, with equivalent facets drawn as clusters.
+    pub fn to_dot(&self) -> String {
+        let quote = |f: &str| format!("\"{}\"", f.replace('\\', "\\\\").replace('"', "\\\""));
+
+        let mut out = "digraph implications {\n".to_owned();
+        for (i, class) in self.equivalent.iter().enumerate() {
+            out.push_str(&format!("  subgraph cluster_{i} {{\n"));
+            for facet in class {
+                out.push_str(&format!("    {};\n", quote(facet)));
+            }
+            out.push_str("  }\n");
+        }
+        for facet in &self.facets {
+            out.push_str(&format!("  {};\n", quote(facet)));
+        }
+        for (f, g) in &self.implications {
+            out.push_str(&format!("  {} -> {};\n", quote(f), quote(g)));
+        }
+        out.push_str("}\n");
+
+        out
+    }
+
+    /// Returns graph in JSON format.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "facets": self.facets,
+            "implications": self.implications,
+            "equivalent": self.equivalent,
+            "exclusive": self.exclusive,
+        })
+        .to_string()
+    }
+}
+
+/// Computing implications among facets.
+pub trait Implications {
+    /// Returns [ImplicationGraph](ImplicationGraph) of facets under **route**, considering
+    /// shown atoms only, if **projecting** is set.
+    ///
+    /// Consequences under **route** are computed once, and each atom is solved for only if it
+    /// is neither equivalent nor complementary to an atom solved for before.
+    fn implication_graph<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        projecting: bool,
+    ) -> Option<ImplicationGraph>;
+}
+impl<B: SolverBackend> Implications for Navigator<B> {
+    fn implication_graph<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        projecting: bool,
+    ) -> Option<ImplicationGraph> {
+        let route = self.route(route);
+        let before = brave_and_cautious(self, &route, projecting)?;
+        let (bcs, ccs) = &before;
+        let atoms = bcs.difference(ccs).cloned().collect::<Vec<_>>();
+        let facets = atoms
+            .iter()
+            .flat_map(|a| [a.clone(), negate(a)])
+            .collect::<Vec<_>>();
+        let index = facets
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (f.clone(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut edges: Vec<Option<BTreeSet<usize>>> = vec![None; facets.len()];
+        for i in (0..facets.len()).step_by(2) {
+            if edges[i].is_some() {
+                continue;
+            }
+            for j in [i, i + 1] {
+                let p = preview(self, &route, &before, &facets[j], projecting)?;
+                let forced = p
+                    .forced_true
+                    .into_iter()
+                    .chain(p.forced_false.iter().map(|a| negate(a)))
+                    .filter_map(|g| index.get(&g).copied())
+                    .collect();
+                edges[j] = Some(forced);
+            }
+
+            // atoms equivalent or complementary to the one of facet i share its implications
+            let (pos, neg) = (edges[i].clone()?, edges[i + 1].clone()?);
+            for k in (i + 2..facets.len()).step_by(2) {
+                let (same, opposite) = match (pos.contains(&k), neg.contains(&(k + 1))) {
+                    (true, true) => (i, i + 1),
+                    _ if pos.contains(&(k + 1)) && neg.contains(&k) => (i + 1, i),
+                    _ => continue,
+                };
+                edges[k] = Some(substitute(&edges[same], k, same));
+                edges[k + 1] = Some(substitute(&edges[opposite], k + 1, opposite));
+            }
+        }
+        let edges = edges
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect::<Vec<_>>();
+
+        let mut class = (0..facets.len()).collect::<Vec<_>>();
+        for (i, targets) in edges.iter().enumerate() {
+            for j in targets.iter().copied().filter(|j| *j > i) {
+                if edges[j].contains(&i) {
+                    let (a, b) = (find(&mut class, i), find(&mut class, j));
+                    class[a.max(b)] = a.min(b);
+                }
+            }
+        }
+        let mut equivalent: Vec<Vec<String>> = vec![];
+        let mut representatives: Vec<usize> = vec![];
+        for (i, facet) in facets.iter().enumerate() {
+            let r = find(&mut class, i);
+            match representatives.iter().position(|x| *x == r) {
+                Some(c) => equivalent[c].push(facet.clone()),
+                None => {
+                    representatives.push(r);
+                    equivalent.push(vec![facet.clone()]);
+                }
+            }
+        }
+        equivalent.retain(|c| c.len() > 1);
+
+        let implications = edges
+            .iter()
+            .enumerate()
+            .flat_map(|(i, targets)| targets.iter().map(move |j| (i, *j)))
+            .map(|(i, j)| (facets[i].clone(), facets[j].clone()))
+            .collect();
+        let exclusive = (0..atoms.len())
+            .flat_map(|a| (a + 1..atoms.len()).map(move |b| (a, b)))
+            .filter(|(a, b)| edges[2 * a].contains(&(2 * b + 1)))
+            .map(|(a, b)| (atoms[a].clone(), atoms[b].clone()))
+            .collect();
+
+        Some(ImplicationGraph {
+            facets,
+            implications,
+            equivalent,
+            exclusive,
+        })
+    }
+}
+
+fn negate(atom: &str) -> String {
+    format!("~{atom}")
+}
+
+/// Returns implications of a facet **k** with the same solutions as facet **i** with
+/// implications **edges**.
+fn substitute(edges: &Option<BTreeSet<usize>>, k: usize, i: usize) -> BTreeSet<usize> {
+    let mut edges = edges.clone().unwrap_or_default();
+    edges.remove(&k);
+    edges.insert(i);
+
+    edges
+}
+
+fn find(class: &mut [usize], i: usize) -> usize {
+    let mut r = i;
+    while class[r] != r {
+        r = class[r];
+    }
+    class[i] = r;
+
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::fixtures::toy;
+    use crate::nav::errors::{NavigatorError, Result};
+
+    fn pairs(xs: &[(&str, &str)]) -> Vec<(String, String)> {
+        xs.iter()
+            .map(|(f, g)| (f.to_string(), g.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn build_implication_graph() -> Result<()> {
+        let mut nav = toy()?;
+
+        let graph = nav
+            .implication_graph(std::iter::empty::<String>(), false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            graph.facets,
+            vec!["a", "~a", "b", "~b", "c", "~c", "d", "~d"]
+        );
+        assert_eq!(
+            graph.implications,
+            pairs(&[
+                ("a", "~b"),
+                ("a", "~c"),
+                ("a", "~d"),
+                ("~a", "b"),
+                ("b", "~a"),
+                ("~b", "a"),
+                ("~b", "~c"),
+                ("~b", "~d"),
+                ("c", "~a"),
+                ("c", "b"),
+                ("c", "~d"),
+                ("d", "~a"),
+                ("d", "b"),
+                ("d", "~c"),
+            ])
+        );
+        assert_eq!(
+            graph.equivalent,
+            vec![
+                vec!["a".to_owned(), "~b".to_owned()],
+                vec!["~a".to_owned(), "b".to_owned()]
+            ]
+        );
+        assert_eq!(
+            graph.exclusive,
+            pairs(&[("a", "b"), ("a", "c"), ("a", "d"), ("c", "d")])
+        );
+
+        let graph = nav
+            .implication_graph(["b"].iter(), false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            graph.implications,
+            pairs(&[("c", "~d"), ("~c", "d"), ("d", "~c"), ("~d", "c")])
+        );
+        assert_eq!(graph.equivalent.len(), 2);
+
+        let graph = nav
+            .implication_graph(["a"].iter(), false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(graph, ImplicationGraph::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn export_implication_graph() -> Result<()> {
+        let graph = ImplicationGraph {
+            facets: vec!["p(\"x\")".to_owned(), "~p(\"x\")".to_owned()],
+            implications: vec![],
+            equivalent: vec![],
+            exclusive: vec![],
+        };
+        assert_eq!(
+            graph.to_dot(),
+            "digraph implications {\n  \"p(\\\"x\\\")\";\n  \"~p(\\\"x\\\")\";\n}\n"
+        );
+
+        let graph = toy()?
+            .implication_graph(["b"].iter(), false)
+            .ok_or(NavigatorError::None)?;
+        let dot = graph.to_dot();
+        assert!(dot.contains("subgraph cluster_0 {\n    \"c\";\n    \"~d\";\n  }"));
+        assert!(dot.contains("  \"c\" -> \"~d\";\n"));
+
+        #[cfg(feature = "json")]
+        assert_eq!(
+            graph.to_json(),
+            concat!(
+                r#"{"equivalent":[["c","~d"],["~c","d"]],"exclusive":[["c","d"]],"#,
+                r#""facets":["c","~c","d","~d"],"#,
+                r#""implications":[["c","~d"],["~c","d"],["d","~c"],["~d","c"]]}"#
+            )
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn build_implication_graph_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let graph = nav
+            .implication_graph(std::iter::empty::<String>(), false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(graph.implications.len(), 14);
+        assert_eq!(graph.equivalent.len(), 2);
+        assert_eq!(graph.exclusive.len(), 4);
+
+        Ok(())
+    }
+}
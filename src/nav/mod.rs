@@ -1,43 +1,184 @@
+pub mod backend;
+pub mod diagnosis;
 pub mod errors;
 pub mod facets;
+#[cfg(feature = "clingo")]
+pub mod grouping;
+pub mod implications;
+pub mod pareto;
+pub mod persistence;
+pub mod preferences;
+pub mod preview;
+pub mod redundancy;
+pub mod reference;
+pub mod sampling;
 pub mod soe;
 mod utils;
 pub mod weights;
 
+#[cfg(feature = "clingo")]
 use crate::lex;
 
+use backend::{Interrupt, Mode, Optimization, SolverBackend};
 use errors::Result;
-
-use clingo::{Control, Part, SolverLiteral, Symbol};
-use std::collections::{HashMap, HashSet};
-
-use self::errors::NavigatorError;
-
-pub struct Navigator {
+use preferences::Preferences;
+#[cfg(feature = "clingo")]
+use utils::ToHashSet;
+
+#[cfg(feature = "clingo")]
+use backend::Clingo;
+#[cfg(feature = "clingo")]
+use clingo::Symbol;
+use std::collections::HashMap;
+#[cfg(feature = "clingo")]
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// Solution paired with its cost, in order of decreasing priority.
+pub type Costed<A> = (Vec<A>, Vec<i64>);
+
+#[cfg(feature = "clingo")]
+type DefaultBackend = Clingo;
+#[cfg(not(feature = "clingo"))]
+type DefaultBackend = backend::Memory;
+
+/// Navigates answer sets of a program solved by [SolverBackend](backend::SolverBackend)
+/// **B**, which is clingo by default, or [Memory](backend::Memory) without feature `clingo`.
+pub struct Navigator<B: SolverBackend = DefaultBackend> {
     source: (String, Vec<String>),
-    ctl: Option<Control>,
-    literals: HashMap<Symbol, SolverLiteral>,
+    backend: B,
+    literals: HashMap<B::Atom, B::Literal>,
+    optimization: Optimization,
 }
+#[cfg(feature = "clingo")]
 impl Navigator {
     /// Constructs [Navigator](Navigator) over answer set program specified by **source**.
     ///
     /// The underlying clingo solver uses arguments specified in **args**.
     pub fn new(source: impl Into<String>, args: Vec<String>) -> Result<Self> {
-        let mut ctl = clingo::control(args.clone())?;
+        let mut nav = Self::with_backend(Clingo::new(args.clone())?, source)?;
+        nav.source.1 = args;
 
-        let lp = source.into();
-        ctl.add("base", &[], &lp)?;
-        ctl.ground(&[Part::new("base", vec![])?])?;
+        Ok(nav)
+    }
 
-        let mut literals = HashMap::new();
-        for atom in ctl.symbolic_atoms()?.iter()? {
-            literals.insert(atom.symbol()?, atom.literal()?);
-        }
+    /// Returns signatures `(name, arity)` of atoms of ground program.
+    ///
+    /// Names of classically negated atoms are prefixed by `-`, and tuples have the empty name.
+    pub fn symbols(&self) -> impl Iterator<Item = (String, usize)> + '_ {
+        self.literals
+            .keys()
+            .filter_map(|s| lex::signature(*s))
+            .collect::<HashSet<_>>()
+            .into_iter()
+    }
+
+    /// Returns number of atoms of ground program per signature `(name, arity)`.
+    pub fn signature_counts(&self) -> HashMap<(String, usize), usize> {
+        let mut counts = HashMap::new();
+        self.literals
+            .keys()
+            .filter_map(|s| lex::signature(*s))
+            .for_each(|sig| *counts.entry(sig).or_insert(0) += 1);
+
+        counts
+    }
+
+    /// Returns atoms of ground program with signature **name**/**arity**.
+    pub fn atoms_with_signature<'a>(
+        &'a self,
+        name: &'a str,
+        arity: usize,
+    ) -> impl Iterator<Item = Symbol> + 'a {
+        self.literals
+            .keys()
+            .filter(move |s| lex::signature(**s).is_some_and(|(n, a)| n == name && a == arity))
+            .copied()
+    }
+
+    /// Returns atoms of ground program matching **pattern**, e.g., `p(X, 3)`.
+    pub fn atoms_matching(&self, pattern: &str) -> Result<Vec<Symbol>> {
+        let pattern = lex::parse_pattern(pattern)?;
+
+        Ok(self
+            .literals
+            .keys()
+            .filter(|s| pattern.matches(**s))
+            .copied()
+            .collect())
+    }
+
+    /// Returns atoms of ground program that are shown in some solution.
+    pub fn shown_atoms(&mut self) -> Result<Vec<Symbol>> {
+        let (_, shown) = self.backend.brave_atoms()?;
+
+        Ok(shown
+            .into_iter()
+            .filter(|s| self.literals.contains_key(s))
+            .collect())
+    }
+
+    /// Returns atoms of ground program that are true in some solution, but never shown.
+    pub fn hidden_atoms(&mut self) -> Result<Vec<Symbol>> {
+        let (atoms, shown) = self.backend.brave_atoms()?;
+        let shown = shown.to_hashset();
+
+        Ok(atoms.into_iter().filter(|s| !shown.contains(s)).collect())
+    }
+
+    /// Adds specified `rule` from logic program.
+    pub fn add_rule<S: std::fmt::Display>(&mut self, rule: S) -> Result<()> {
+        let (source, args) = &self.source;
+        let new_source = format!("{}\n{rule}", source);
+        *self = Navigator::new(new_source, args.to_vec())?;
+
+        Ok(())
+    }
+
+    /// Removes specified `rule` from logic program.
+    pub fn remove_rule<S: ToString>(&mut self, rule: S) -> Result<()> {
+        let (source, args) = &self.source;
+        let new_source = source.replace(&rule.to_string(), "");
+        *self = Navigator::new(new_source, args.to_vec())?;
+
+        Ok(())
+    }
+
+    /// Adds specified `argument`.
+    pub fn add_arg<S: std::fmt::Display>(&mut self, arg: S) -> Result<()> {
+        let (source, args) = &self.source;
+        let mut new_args = args.clone();
+        new_args.push(arg.to_string());
+        *self = Navigator::new(source, new_args.to_vec())?;
+
+        Ok(())
+    }
+
+    /// Removes specified `argument`.
+    pub fn remove_arg<S: std::fmt::Display>(&mut self, arg: S) -> Result<()> {
+        let (source, args) = &self.source;
+        let mut new_args = args.clone();
+        new_args.push(arg.to_string());
+        let i = args.iter().position(|x| *x == arg.to_string()).unwrap();
+        new_args.remove(i);
+        *self = Navigator::new(source, new_args.to_vec())?;
+
+        Ok(())
+    }
+}
+impl<B: SolverBackend> Navigator<B> {
+    /// Constructs [Navigator](Navigator) over answer set program specified by **source**,
+    /// grounded and solved by **backend**.
+    pub fn with_backend(mut backend: B, source: impl Into<String>) -> Result<Self> {
+        let lp = source.into();
+        backend.ground(&lp)?;
+        let literals = backend.atoms()?.into_iter().collect();
 
         Ok(Self {
-            source: (lp, args),
-            ctl: Some(ctl),
+            source: (lp, vec![]),
+            backend,
             literals,
+            optimization: Optimization::default(),
         })
     }
 
@@ -53,49 +194,44 @@ impl Navigator {
         upper_bound: Option<usize>,
         route: impl Iterator<Item = S>,
     ) -> Result<usize> {
-        let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-        let ctx = route.map(|s| self.expression_to_literal(s)).flatten();
-        let mut handle = ctl.solve(clingo::SolveMode::YIELD, &ctx.collect::<Vec<_>>())?;
-        let mut i = 0;
+        self.enumerate(upper_bound, route, false)
+    }
 
-        match upper_bound {
-            None => {
-                while let Ok(Some(answer_set)) = handle.model() {
-                    println!("solution {:?}: ", i + 1);
-                    let atoms = answer_set.symbols(clingo::ShowType::SHOWN)?;
-                    atoms.iter().for_each(|atom| {
-                        print!("{} ", atom.to_string());
-                    });
-                    println!();
-
-                    i += 1;
-                    handle.resume()?;
-                }
-            }
-            Some(n) => {
-                while let Ok(Some(answer_set)) = handle.model() {
-                    println!("solution {:?}: ", i + 1);
-                    let atoms = answer_set.symbols(clingo::ShowType::SHOWN)?;
-                    atoms.iter().for_each(|atom| {
-                        print!("{} ", atom.to_string());
-                    });
-                    println!();
-
-                    i += 1;
-                    if i == n {
-                        break;
-                    }
-                    handle.resume()?;
-                }
-            }
-        }
+    /// Enumerates solutions under current route extended by facets in **route**, projected onto
+    /// shown atoms.
+    ///
+    /// Will enumerate all existing projected solutions, if **upper_bound** is
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None).
+    /// Otherwise, enumeration stops after **upper_bound** was reached.
+    ///
+    /// Prints the projected solutions, and returns the number of enumerated projected solutions.
+    pub fn enumerate_solutions_projecting<S: ToString>(
+        &mut self,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+    ) -> Result<usize> {
+        self.enumerate(upper_bound, route, true)
+    }
 
-        let ctl = handle
-            .close()
-            .map_err(|e| errors::NavigatorError::Clingo(e))?;
-        self.ctl = Some(ctl);
+    fn enumerate<S: ToString>(
+        &mut self,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+        projecting: bool,
+    ) -> Result<usize> {
+        let route = self.route(route);
+        let mut i = 0;
+        self.backend
+            .solve(&route, Mode::Models, projecting, |atoms| {
+                println!("solution {:?}: ", i + 1);
+                atoms.iter().for_each(|atom| print!("{atom} "));
+                println!();
+
+                i += 1;
+                upper_bound != Some(i)
+            })?;
 
-        return Ok(i);
+        Ok(i)
     }
 
     /// Enumerates solutions under current route extended by facets in **route** and projected onto
@@ -112,57 +248,22 @@ impl Navigator {
         route: impl Iterator<Item = S>,
         project_on: Vec<String>,
     ) -> Result<usize> {
-        let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-        let ctx = route.map(|s| self.expression_to_literal(s)).flatten();
-        let mut handle = ctl.solve(clingo::SolveMode::YIELD, &ctx.collect::<Vec<_>>())?;
+        let route = self.route(route);
         let mut i = 0;
-
-        match upper_bound {
-            None => {
-                while let Ok(Some(answer_set)) = handle.model() {
-                    println!("solution {:?}: ", i + 1);
-                    let atoms = answer_set.symbols(clingo::ShowType::SHOWN)?;
-                    atoms
-                        .iter()
-                        .map(|atom| atom.to_string())
-                        .filter(|atom| project_on.contains(atom))
-                        .for_each(|atom| {
-                            print!("{} ", atom);
-                        });
-                    println!();
-
-                    i += 1;
-                    handle.resume()?;
-                }
-            }
-            Some(n) => {
-                while let Ok(Some(answer_set)) = handle.model() {
-                    println!("solution {:?}: ", i + 1);
-                    let atoms = answer_set.symbols(clingo::ShowType::SHOWN)?;
-                    atoms
-                        .iter()
-                        .map(|atom| atom.to_string())
-                        .filter(|atom| project_on.contains(atom))
-                        .for_each(|atom| {
-                            print!("{} ", atom);
-                        });
-                    println!();
-
-                    i += 1;
-                    if i == n {
-                        break;
-                    }
-                    handle.resume()?;
-                }
-            }
-        }
-
-        let ctl = handle
-            .close()
-            .map_err(|e| errors::NavigatorError::Clingo(e))?;
-        self.ctl = Some(ctl);
-
-        return Ok(i);
+        self.backend.solve(&route, Mode::Models, false, |atoms| {
+            println!("solution {:?}: ", i + 1);
+            atoms
+                .iter()
+                .map(|atom| atom.to_string())
+                .filter(|atom| project_on.contains(atom))
+                .for_each(|atom| print!("{atom} "));
+            println!();
+
+            i += 1;
+            upper_bound != Some(i)
+        })?;
+
+        Ok(i)
     }
 
     /// Enumerates solutions under current route extended by facets in **route**
@@ -173,119 +274,39 @@ impl Navigator {
     /// Otherwise, enumeration stops after **upper_bound** was reached.
     ///
     /// Returns one JSON per solution.
-    #[allow(unused_assignments)]
     pub fn enumerate_solutions_outf2<S: ToString>(
         &mut self,
         upper_bound: Option<usize>,
         route: impl Iterator<Item = S>,
     ) -> Result<Vec<String>> {
-        let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-        let ctx = route.map(|s| self.expression_to_literal(s)).flatten();
-        let mut handle = ctl.solve(clingo::SolveMode::YIELD, &ctx.collect::<Vec<_>>())?;
-        let mut i = 0;
-
+        let route = self.route(route);
         let mut out = vec![];
+        self.backend.solve(&route, Mode::Models, false, |atoms| {
+            print!(".");
+            out.push(outf2(atoms));
 
-        match upper_bound {
-            None => {
-                while let Ok(Some(answer_set)) = handle.model() {
-                    print!(".");
-                    let atoms = answer_set.symbols(clingo::ShowType::SHOWN)?;
-                    let mut answer_set = "{\"Solver\": \"\", \"Input\": [\"\"], ".to_owned();
-                    answer_set =
-                        format!("{answer_set}\"Call\": [ {{ \"Witnesses\": [ {{ \"Value\": [");
-                    if let Some((last, rest)) = atoms.split_last() {
-                        for atom in rest {
-                            answer_set = format!("{answer_set}{:?}, ", atom.to_string());
-                        }
-                        answer_set = format!(
-                            "{answer_set}{:?}]}}]}}],\n\"Result\":\"SATISFIABLE\",{}{}{}",
-                            last.to_string(),
-                            "\n\"Models\":{\"Number\":1,\"More\":\"yes\"},\n\"Calls\": 1,\n",
-                            "\"Time\":{\"Total\": 0.000,\"Solve\": 0.000,",
-                            "\"Model\": 0.000,\"Unsat\": 0.000,\"CPU\": 0.000}}\n"
-                        );
-                    }
-
-                    i += 1;
-                    out.push(answer_set);
-                    handle.resume()?;
-                }
-            }
-            Some(n) => {
-                while let Ok(Some(answer_set)) = handle.model() {
-                    print!(".");
-                    let atoms = answer_set.symbols(clingo::ShowType::SHOWN)?;
-                    let mut answer_set = "{\"Solver\": \"\", \"Input\": [\"\"], ".to_owned();
-                    answer_set =
-                        format!("{answer_set}\"Call\": [ {{ \"Witnesses\": [ {{ \"Value\": [");
-                    if let Some((last, rest)) = atoms.split_last() {
-                        for atom in rest {
-                            answer_set = format!("{answer_set}{:?}, ", atom.to_string());
-                        }
-                        answer_set = format!(
-                            "{answer_set}{:?}]}}]}}],\n\"Result\":\"SATISFIABLE\",{}{}{}",
-                            last.to_string(),
-                            "\n\"Models\":{\"Number\":1,\"More\":\"yes\"},\n\"Calls\": 1,\n",
-                            "\"Time\":{\"Total\": 0.000,\"Solve\": 0.000,",
-                            "\"Model\": 0.000,\"Unsat\": 0.000,\"CPU\": 0.000}}\n"
-                        );
-                    }
-
-                    i += 1;
-                    out.push(answer_set);
-                    if i == n {
-                        break;
-                    }
-                    handle.resume()?;
-                }
-            }
-        }
+            upper_bound != Some(out.len())
+        })?;
 
-        let ctl = handle
-            .close()
-            .map_err(|e| errors::NavigatorError::Clingo(e))?;
-        self.ctl = Some(ctl);
-
-        return Ok(out);
+        Ok(out)
     }
 
-    ///
-    #[allow(unused_assignments)]
+    /// Returns some solution under current route extended by facets in **route**, if there is
+    /// one.
     pub fn one_or_none<S: ToString>(
         &mut self,
         route: impl Iterator<Item = S>,
     ) -> Option<Vec<String>> {
-        let ctl = self.ctl.take().ok_or(NavigatorError::NoControl).unwrap();
-        let ctx = route.map(|s| self.expression_to_literal(s)).flatten();
-        let mut handle = ctl
-            .solve(clingo::SolveMode::YIELD, &ctx.collect::<Vec<_>>())
-            .unwrap();
-
-        if let Ok(Some(answer_set)) = handle.model() {
-            let x = Some(
-                answer_set
-                    .symbols(clingo::ShowType::SHOWN).unwrap()
-                    .clone()
-                    .iter()
-                    .map(|a| a.to_string())
-                    .collect::<Vec<_>>(),
-            );
-
-            let ctl = handle
-                .close()
-                .map_err(|e| errors::NavigatorError::Clingo(e)).unwrap();
-            self.ctl = Some(ctl);
-
-            x
-        } else {
-            let ctl = handle
-                .close()
-                .map_err(|e| errors::NavigatorError::Clingo(e)).unwrap();
-            self.ctl = Some(ctl);
-
-            None
-        }
+        let route = self.route(route);
+        let mut solution = None;
+        self.backend
+            .solve(&route, Mode::Models, false, |atoms| {
+                solution = Some(atoms.iter().map(|a| a.to_string()).collect());
+                false
+            })
+            .ok()?;
+
+        solution
     }
 
     /// Enumerates solutions under current route extended by facets in **route**, quietly.
@@ -300,102 +321,180 @@ impl Navigator {
         upper_bound: Option<usize>,
         route: impl Iterator<Item = S>,
     ) -> Result<usize> {
-        let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-        let ctx = route.map(|s| self.expression_to_literal(s)).flatten();
-        let mut handle = ctl.solve(clingo::SolveMode::YIELD, &ctx.collect::<Vec<_>>())?;
-
+        let route = self.route(route);
         let mut i = 0;
+        self.backend.solve(&route, Mode::Models, false, |_| {
+            i += 1;
+            upper_bound != Some(i)
+        })?;
 
-        match upper_bound {
-            None => {
-                while let Ok(Some(_)) = handle.model() {
-                    i += 1;
-                    handle.resume()?;
-                }
-            }
-            Some(n) => {
-                while let Ok(Some(_)) = handle.model() {
-                    i += 1;
-                    if i == n {
-                        break;
-                    }
-                    handle.resume()?;
+        Ok(i)
+    }
+
+    /// Returns solutions under current route extended by facets in **route**.
+    ///
+    /// Will return all existing solutions, if **upper_bound** is
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None).
+    /// Otherwise, enumeration stops after **upper_bound** was reached.
+    pub fn solutions<S: ToString>(
+        &mut self,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<Vec<B::Atom>>> {
+        self.collect_solutions(0, upper_bound, route, false)
+    }
+
+    /// Returns solutions under current route extended by facets in **route**, projected onto
+    /// shown atoms.
+    ///
+    /// Will return all existing projected solutions, if **upper_bound** is
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None).
+    /// Otherwise, enumeration stops after **upper_bound** was reached.
+    pub fn solutions_projecting<S: ToString>(
+        &mut self,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<Vec<B::Atom>>> {
+        self.collect_solutions(0, upper_bound, route, true)
+    }
+
+    /// Returns solutions under current route extended by facets in **route**, skipping the first
+    /// **offset** solutions without storing them.
+    ///
+    /// Will return all remaining solutions, if **upper_bound** is
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None).
+    /// Otherwise, enumeration stops after **upper_bound** solutions were returned.
+    pub fn solutions_after<S: ToString>(
+        &mut self,
+        offset: usize,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<Vec<B::Atom>>> {
+        self.collect_solutions(offset, upper_bound, route, false)
+    }
+
+    fn collect_solutions<S: ToString>(
+        &mut self,
+        offset: usize,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+        projecting: bool,
+    ) -> Result<Vec<Vec<B::Atom>>> {
+        let route = self.route(route);
+        let mut out = vec![];
+        let mut skipped = 0;
+        self.backend
+            .solve(&route, Mode::Models, projecting, |atoms| {
+                if skipped < offset {
+                    skipped += 1;
+                    return true;
                 }
-            }
-        }
+                out.push(atoms.to_vec());
+                upper_bound != Some(out.len())
+            })?;
+
+        Ok(out)
+    }
+
+    /// Returns solutions closest to current route extended by facets in **route**, paired with
+    /// the facets of **route** they violate.
+    ///
+    /// Closest solutions violate the least number of facets, i.e., have the least Hamming
+    /// distance to **route** read as partial assignment, so under routes with solutions, these
+    /// are just the solutions. Which facets to violate is found from cores of **route**, like
+    /// preferences in [preferred](preferences::Preferences::preferred). Will return all closest
+    /// solutions, if **upper_bound** is
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None).
+    /// Otherwise, enumeration stops after **upper_bound** was reached.
+    pub fn nearest_solutions<S: ToString>(
+        &mut self,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<(Vec<String>, Vec<String>)>> {
+        let preferred = self.preferred(std::iter::empty::<String>(), route, upper_bound)?;
 
-        let ctl = handle
-            .close()
-            .map_err(|e| errors::NavigatorError::Clingo(e))?;
-        self.ctl = Some(ctl);
+        Ok(preferred.map(|p| p.solutions).unwrap_or_default())
+    }
 
-        return Ok(i);
+    /// Returns solutions under current route extended by facets in **route**, paired with their
+    /// cost in order of decreasing priority.
+    ///
+    /// Unlike [solutions](Navigator::solutions), all models count as solutions, regardless of
+    /// [optimize](Navigator::optimize). Will return all existing solutions, if **upper_bound** is
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None).
+    /// Otherwise, enumeration stops after **upper_bound** was reached.
+    pub fn solutions_with_costs<S: ToString>(
+        &mut self,
+        upper_bound: Option<usize>,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<Costed<B::Atom>>> {
+        let route = self.route(route);
+        let mut out = vec![];
+        self.backend.solve_with_costs(&route, |atoms, cost| {
+            out.push((atoms.to_vec(), cost.to_vec()));
+            upper_bound != Some(out.len())
+        })?;
+
+        Ok(out)
     }
 
     /// Checks whether **atom** is part of herbrand base.
     pub fn is_known(&self, atom: String) -> Option<bool> {
-        lex::parse(&atom).map(|x| self.literals.keys().any(|y| *y == x))
+        self.backend
+            .parse_atom(&atom)
+            .map(|x| self.literals.contains_key(&x))
     }
 
     /// Returns atoms of ground program.
     pub fn atoms(&self) -> impl Iterator<Item = String> + '_ {
-        self.literals.keys().map(|sym| sym.to_string())
-    }
-
-    /// Returns atoms of ground program.
-    pub fn symbols(&self) -> impl Iterator<Item = (String, usize)> + '_ {
-        self.literals
-            .keys()
-            .map(|s| (s.name().unwrap().to_owned(), s.arguments().unwrap().len()))
-            .collect::<HashSet<_>>()
-            .into_iter()
+        self.literals.keys().map(|atom| atom.to_string())
     }
 
-    /// Adds specified `rule` from logic program.
-    pub fn add_rule<S: std::fmt::Display>(&mut self, rule: S) -> Result<()> {
-        let (source, args) = &self.source;
-        let new_source = format!("{}\n{rule}", source);
-        *self = Navigator::new(new_source, args.to_vec())?;
+    /// Returns underlying logic program.
+    pub fn program(&self) -> String {
+        let (source, _) = &self.source;
 
-        Ok(())
+        source.to_owned()
     }
 
-    /// Removes specified `rule` from logic program.
-    pub fn remove_rule<S: ToString>(&mut self, rule: S) -> Result<()> {
-        let (source, args) = &self.source;
-        let new_source = source.replace(&rule.to_string(), "");
-        *self = Navigator::new(new_source, args.to_vec())?;
+    /// Returns arguments the underlying solver was configured by.
+    pub fn args(&self) -> &[String] {
+        &self.source.1
+    }
 
-        Ok(())
+    /// Returns handle to interrupt solving of the navigator, e.g., from another thread.
+    pub fn interrupt_handle(&self) -> Interrupt {
+        self.backend.interrupt_handle()
     }
 
-    /// Adds specified `argument`.
-    pub fn add_arg<S: std::fmt::Display>(&mut self, arg: S) -> Result<()> {
-        let (source, args) = &self.source;
-        let mut new_args = args.clone();
-        new_args.push(arg.to_string());
-        *self = Navigator::new(source, new_args.to_vec())?;
+    /// Restricts navigation to models according to **optimization**, e.g., to optimal ones.
+    ///
+    /// Affects solutions, consequences, facets and weights alike.
+    pub fn optimize(&mut self, optimization: Optimization) -> Result<()> {
+        self.backend.optimize(optimization.clone())?;
+        self.optimization = optimization;
 
         Ok(())
     }
 
-    /// Removes specified `argument`.
-    pub fn remove_arg<S: std::fmt::Display>(&mut self, arg: S) -> Result<()> {
-        let (source, args) = &self.source;
-        let mut new_args = args.clone();
-        new_args.push(arg.to_string());
-        let i = args.iter().position(|x| *x == arg.to_string()).unwrap();
-        new_args.remove(i);
-        *self = Navigator::new(source, new_args.to_vec())?;
-
-        Ok(())
+    /// Returns which models navigation is restricted to.
+    pub fn optimization(&self) -> &Optimization {
+        &self.optimization
     }
 
-    /// Returns underlying logic program.
-    pub fn program(&self) -> String {
-        let (source, _) = &self.source;
+    /// Returns cost of optimal models under current route extended by facets in **route**, or
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if there are
+    /// no models.
+    ///
+    /// Costs are given in order of decreasing priority, and are empty for programs without
+    /// optimization statements.
+    pub fn optimum<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Option<Vec<i64>>> {
+        let route = self.route(route);
 
-        source.to_owned()
+        self.backend.optimum(&route)
     }
 
     /// Resets current program to initial program.
@@ -405,34 +504,67 @@ impl Navigator {
         source.to_owned()
     }
 }
-impl Navigator {
-    #[allow(unused)]
-    fn assume(&mut self, route: &[SolverLiteral]) -> Result<()> {
-        let mut ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-        let res = ctl
-            .backend()
-            .and_then(|mut b| b.assume(route))
-            .map_err(|e| errors::NavigatorError::Clingo(e));
-        res
-    }
-
-    fn expression_to_literal(&self, expression: impl ToString) -> Option<SolverLiteral> {
-        let s = expression.to_string();
-        match s.starts_with('~') {
-            true => lex::parse(&s[1..])
-                .as_ref()
-                .and_then(|symbol| self.literals.get(symbol).map(|atom| atom.negate())),
-            _ => lex::parse(&s)
-                .as_ref()
-                .and_then(|symbol| self.literals.get(symbol))
-                .copied(),
+impl<B: SolverBackend> Navigator<B> {
+    /// Returns literals of facets in **route**, skipping unknown atoms.
+    fn route<S: ToString>(&self, route: impl Iterator<Item = S>) -> Vec<B::Literal> {
+        route
+            .filter_map(|s| self.expression_to_literal(s))
+            .collect()
+    }
+
+    /// Returns facets of **route** over known atoms together with their literals, dropping
+    /// duplicates.
+    fn facets<S: ToString>(&self, route: impl Iterator<Item = S>) -> Vec<(String, B::Literal)> {
+        let mut facets: Vec<(String, B::Literal)> = vec![];
+        for facet in route.map(|f| f.to_string().trim().to_owned()) {
+            if let Some(literal) = self.expression_to_literal(&facet) {
+                if !facets.iter().any(|(f, _)| *f == facet) {
+                    facets.push((facet, literal));
+                }
+            }
+        }
+
+        facets
+    }
+
+    fn expression_to_literal(&self, expression: impl ToString) -> Option<B::Literal> {
+        let expression = expression.to_string();
+        let (positive, atom) = match expression.trim().strip_prefix('~') {
+            Some(atom) => (false, atom.trim()),
+            None => (true, expression.trim()),
+        };
+        let atom = self.backend.parse_atom(atom)?;
+        self.literals.get(&atom).map(|literal| match positive {
+            true => *literal,
+            _ => self.backend.negate(*literal),
+        })
+    }
+}
+
+/// Formats **atoms** as solution in format required by clingraph.
+pub(crate) fn outf2(atoms: &[impl Display]) -> String {
+    let mut answer_set = "{\"Solver\": \"\", \"Input\": [\"\"], ".to_owned();
+    answer_set = format!("{answer_set}\"Call\": [ {{ \"Witnesses\": [ {{ \"Value\": [");
+    if let Some((last, rest)) = atoms.split_last() {
+        for atom in rest {
+            answer_set = format!("{answer_set}{:?}, ", atom.to_string());
         }
+        answer_set = format!(
+            "{answer_set}{:?}]}}]}}],\n\"Result\":\"SATISFIABLE\",{}{}{}",
+            last.to_string(),
+            "\n\"Models\":{\"Number\":1,\"More\":\"yes\"},\n\"Calls\": 1,\n",
+            "\"Time\":{\"Total\": 0.000,\"Solve\": 0.000,",
+            "\"Model\": 0.000,\"Unsat\": 0.000,\"CPU\": 0.000}}\n"
+        );
     }
+
+    answer_set
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "clingo"))]
 mod tests {
     use super::*;
+    use facets::Facets;
 
     #[test]
     fn startup() {
@@ -469,6 +601,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::single_element_loop)]
     fn enumerate_with_upper_bound() -> Result<()> {
         let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
 
@@ -498,6 +631,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn collect_solutions() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let xs = nav.solutions(None, std::iter::empty::<String>())?;
+        assert_eq!(xs.len(), 3);
+
+        let xs = nav.solutions(Some(1), ["b"].iter())?;
+        assert_eq!(xs.len(), 1);
+        assert!(xs[0].contains(&lex::parse("b")?));
+
+        let xs = nav.solutions(None, ["a", "b"].iter())?;
+        assert!(xs.is_empty());
+
+        let all = nav.solutions(None, std::iter::empty::<String>())?;
+        assert_eq!(
+            nav.solutions_after(1, Some(1), std::iter::empty::<String>())?,
+            all[1..2].to_vec()
+        );
+        assert_eq!(
+            nav.solutions_after(1, None, std::iter::empty::<String>())?,
+            all[1..].to_vec()
+        );
+        assert!(nav
+            .solutions_after(3, None, std::iter::empty::<String>())?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn collect_solutions_projecting() -> Result<()> {
+        let mut nav = Navigator::new(
+            "a;b. c;d :- b. e. #show a/0. #show b/0.",
+            vec!["0".to_string()],
+        )?;
+
+        let xs = nav.solutions_projecting(None, std::iter::empty::<String>())?;
+        assert_eq!(xs.len(), 2);
+        assert_eq!(nav.enumerate_solutions_projecting(None, ["b"].iter())?, 1);
+        assert_eq!(nav.solutions(None, ["b"].iter())?.len(), 2);
+
+        Ok(())
+    }
+
     #[test]
     fn enumerate_outf2() -> Result<()> {
         let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
@@ -520,6 +698,81 @@ mod tests {
             vec!["c".to_string(), "d".to_string()],
         )?;
 
+        Ok(())
+    }
+    #[test]
+    fn signature_queries() -> Result<()> {
+        let mut nav = Navigator::new(
+            "p(1..3). q(X,Y) :- p(X), p(Y), X < Y. {r(X)} :- p(X). -s((1,2)). #show q/2. #show r/1.",
+            vec!["0".to_string()],
+        )?;
+
+        let counts = nav.signature_counts();
+        assert_eq!(counts.get(&("p".to_owned(), 1)), Some(&3));
+        assert_eq!(counts.get(&("q".to_owned(), 2)), Some(&3));
+        assert_eq!(counts.get(&("-s".to_owned(), 1)), Some(&1));
+        assert_eq!(nav.symbols().count(), counts.len());
+
+        assert_eq!(nav.atoms_with_signature("r", 1).count(), 3);
+        assert_eq!(nav.atoms_matching("q(X, 3)")?.len(), 2);
+        assert_eq!(nav.atoms_matching("q(1, _)")?.len(), 2);
+        assert!(nav.atoms_matching("q(X, ").is_err());
+
+        let shown = nav.shown_atoms()?;
+        assert_eq!(shown.len(), 6);
+        assert!(shown.contains(&lex::parse("r(2)")?));
+        let hidden = nav.hidden_atoms()?;
+        assert_eq!(hidden.len(), 4);
+        assert!(hidden.contains(&lex::parse("p(1)")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn navigate_optimal_solutions() -> Result<()> {
+        let mut nav = Navigator::new(
+            "a;b. c;d :- b. e. :~ a. [2@1] :~ c. [1@1] :~ d. [1@2]",
+            vec!["0".to_string()],
+        )?;
+
+        assert_eq!(nav.optimum(std::iter::empty::<String>())?, Some(vec![0, 1]));
+        assert_eq!(nav.optimum(["a"].iter())?, Some(vec![0, 2]));
+        assert_eq!(nav.optimum(["a", "b"].iter())?, None);
+
+        nav.optimize(Optimization::Optimal)?;
+        let n = nav.enumerate_solutions_quietly(None, std::iter::empty::<String>())?;
+        assert_eq!(n, 1);
+        let ccs = nav
+            .cautious_consequences(std::iter::empty::<String>())
+            .ok_or(errors::NavigatorError::None)?;
+        assert_eq!(ccs.len(), 3);
+
+        nav.optimize(Optimization::Within(vec![0, 2]))?;
+        let n = nav.enumerate_solutions_quietly(None, std::iter::empty::<String>())?;
+        assert_eq!(n, 2);
+
+        nav.optimize(Optimization::Any)?;
+        let n = nav.enumerate_solutions_quietly(None, std::iter::empty::<String>())?;
+        assert_eq!(n, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_nearest_solutions() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let nearest = nav.nearest_solutions(None, ["a", "~e", "c"].iter())?;
+        assert_eq!(nearest.len(), 2);
+        assert!(nearest.iter().all(|(_, violated)| violated.len() == 2));
+        assert!(nearest
+            .iter()
+            .all(|(_, violated)| violated.contains(&"~e".to_owned())));
+
+        let nearest = nav.nearest_solutions(None, ["b", "d"].iter())?;
+        assert_eq!(nearest.len(), 1);
+        assert!(nearest[0].1.is_empty());
+
         Ok(())
     }
 }
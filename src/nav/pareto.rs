@@ -0,0 +1,224 @@
+use super::backend::SolverBackend;
+use super::errors::{NavigatorError, Result};
+use super::Navigator;
+use std::collections::BTreeSet;
+
+/// Solution on the Pareto front, i.e., not dominated by any other solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParetoSolution {
+    /// Shown atoms, sorted.
+    pub atoms: Vec<String>,
+    /// Cost in order of decreasing priority.
+    pub cost: Vec<i64>,
+}
+
+/// Effect of activating a facet on the costs achievable under a route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostEffect {
+    /// Activated facet.
+    pub facet: String,
+    /// Distinct costs of the Pareto front under the extended route, sorted.
+    pub front: Vec<Vec<i64>>,
+}
+
+/// Navigating Pareto-optimal solutions of programs with several optimization priorities.
+///
+/// Each priority counts as an objective of its own, and a solution dominates another one, if
+/// its cost is at most the other one's at every priority, and less at some. The front is
+/// searched point by point, regardless of [optimize](Navigator::optimize): the least cost,
+/// lexicographically, of solutions not weakly dominated by points found so far is
+/// Pareto-optimal, so it becomes a bound for the next search, until no solution remains.
+pub trait Pareto {
+    /// Returns solutions under **route** not dominated by any other one, ordered by cost.
+    fn pareto_front<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<ParetoSolution>>;
+    /// Returns [CostEffect](CostEffect) of each facet over an atom true in some but not all
+    /// Pareto-optimal solutions under **route**, ordered like
+    /// [preview_all](super::preview::Lookahead::preview_all).
+    fn pareto_facets<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<CostEffect>>;
+}
+impl<B: SolverBackend> Pareto for Navigator<B> {
+    fn pareto_front<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<ParetoSolution>> {
+        let route = self.route(route);
+        let mut front = vec![];
+        search(&mut self.backend, &route, |cost, atoms| {
+            front.push(ParetoSolution {
+                atoms: atoms.into_iter().collect(),
+                cost: cost.to_vec(),
+            })
+        })?;
+        front.sort_by(|x, y| (&x.cost, &x.atoms).cmp(&(&y.cost, &y.atoms)));
+
+        Ok(front)
+    }
+
+    fn pareto_facets<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Result<Vec<CostEffect>> {
+        let route = self.route(route);
+        let (mut bcs, mut ccs) = (BTreeSet::new(), None::<BTreeSet<String>>);
+        search(&mut self.backend, &route, |_, atoms| {
+            bcs.extend(atoms.iter().cloned());
+            ccs = Some(match ccs.take() {
+                Some(ccs) => ccs.intersection(&atoms).cloned().collect(),
+                None => atoms,
+            });
+        })?;
+
+        let mut effects = vec![];
+        for atom in bcs.difference(&ccs.unwrap_or_default()) {
+            let positive = self
+                .expression_to_literal(atom)
+                .ok_or_else(|| NavigatorError::InvalidInput(atom.clone()))?;
+            for (facet, literal) in [
+                (atom.clone(), positive),
+                (format!("~{atom}"), self.backend.negate(positive)),
+            ] {
+                let mut costs = BTreeSet::new();
+                search(
+                    &mut self.backend,
+                    &[route.as_slice(), &[literal]].concat(),
+                    |cost, _| {
+                        costs.insert(cost.to_vec());
+                    },
+                )?;
+                effects.push(CostEffect {
+                    facet,
+                    front: costs.into_iter().collect(),
+                });
+            }
+        }
+
+        Ok(effects)
+    }
+}
+
+/// Searches the Pareto front under **route**, and passes the cost of each Pareto-optimal
+/// solution together with its shown atoms to **on_solution**.
+fn search<B: SolverBackend>(
+    backend: &mut B,
+    route: &[B::Literal],
+    mut on_solution: impl FnMut(&[i64], BTreeSet<String>),
+) -> Result<()> {
+    let mut front: Vec<Vec<i64>> = vec![];
+    loop {
+        let mut point = None;
+        backend.solve_undominated(route, &front, |atoms, cost| {
+            on_solution(cost, atoms.iter().map(|a| a.to_string()).collect());
+            point = Some(cost.to_vec());
+            true
+        })?;
+        match point {
+            Some(cost) => front.push(cost),
+            None => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::fixtures::strings;
+    use crate::nav::backend::Memory;
+
+    /// Answer sets of `a;b. c;d :- b. e. :~ a. [2@1] :~ c. [1@1] :~ d. [1@2]`.
+    fn toy() -> Result<Navigator<Memory>> {
+        let answer_sets = vec![
+            (vec!["a", "e"], vec![0, 2]),
+            (vec!["b", "c", "e"], vec![0, 1]),
+            (vec!["b", "d", "e"], vec![1, 0]),
+        ];
+        Navigator::with_backend(Memory::with_costs(answer_sets), "")
+    }
+
+    #[test]
+    fn compute_pareto_front() -> Result<()> {
+        let mut nav = toy()?;
+
+        assert_eq!(
+            nav.pareto_front(std::iter::empty::<String>())?,
+            vec![
+                ParetoSolution {
+                    atoms: strings(&["b", "c", "e"]),
+                    cost: vec![0, 1],
+                },
+                ParetoSolution {
+                    atoms: strings(&["b", "d", "e"]),
+                    cost: vec![1, 0],
+                },
+            ]
+        );
+        let front = nav.pareto_front(["~c"].iter())?;
+        assert_eq!(front.len(), 2);
+        assert_eq!(front[0].atoms, strings(&["a", "e"]));
+        assert!(nav.pareto_front(["a", "b"].iter())?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn compute_cost_effects() -> Result<()> {
+        let mut nav = toy()?;
+
+        let effects = nav.pareto_facets(std::iter::empty::<String>())?;
+        assert_eq!(
+            effects,
+            vec![
+                CostEffect {
+                    facet: "c".to_owned(),
+                    front: vec![vec![0, 1]],
+                },
+                CostEffect {
+                    facet: "~c".to_owned(),
+                    front: vec![vec![0, 2], vec![1, 0]],
+                },
+                CostEffect {
+                    facet: "d".to_owned(),
+                    front: vec![vec![1, 0]],
+                },
+                CostEffect {
+                    facet: "~d".to_owned(),
+                    front: vec![vec![0, 1]],
+                },
+            ]
+        );
+        assert!(nav.pareto_facets(["a"].iter())?.is_empty());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn compute_pareto_front_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new(
+            "a;b. c;d :- b. e. :~ a. [2@1] :~ c. [1@1] :~ d. [1@2]",
+            vec!["0".to_string()],
+        )?;
+
+        let front = nav.pareto_front(std::iter::empty::<String>())?;
+        assert_eq!(
+            front.iter().map(|s| s.cost.clone()).collect::<Vec<_>>(),
+            vec![vec![0, 1], vec![1, 0]]
+        );
+        assert_eq!(front[0].atoms, strings(&["b", "c", "e"]));
+        assert_eq!(nav.pareto_facets(["b"].iter())?.len(), 4);
+
+        // solutions of equal cost are each on the front once
+        let mut nav = Navigator::new("1 {a;b;c} 1. :~ c. [1@1]", vec!["0".to_string()])?;
+        let front = nav.pareto_front(std::iter::empty::<String>())?;
+        assert_eq!(front.len(), 2);
+        assert_eq!(front[0].atoms, strings(&["a"]));
+        assert_eq!(front[1].atoms, strings(&["b"]));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+use super::backend::SolverBackend;
+use super::errors::{NavigatorError, Result};
+use super::Navigator;
+
+/// Header of the text form, followed by the program hash.
+const HEADER: &str = "savan/1";
+
+/// Line of the text form after which notes follow verbatim.
+const NOTES: &str = "%%";
+
+/// Returns hash identifying **program** solved with **args**, as 16 hexadecimal digits.
+///
+/// Uses 64-bit FNV-1a, so hashes are stable across platforms and releases.
+pub fn program_hash(program: &str, args: &[String]) -> String {
+    let mut hash = 0xcbf29ce484222325u64;
+    let mut feed = |bytes: &[u8]| {
+        for b in bytes {
+            hash ^= *b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    feed(program.as_bytes());
+    for arg in args {
+        feed(&[0]);
+        feed(arg.as_bytes());
+    }
+
+    format!("{hash:016x}")
+}
+
+/// Route saved together with the identity of the program it navigates.
+///
+/// Serializes to JSON, e.g.,
+/// `{"version":1,"program":"9f3c0a1b2c3d4e5f","route":["a","~b(1)"],"notes":"..."}`, or to a
+/// text form listing the header and program hash, one facet per line, and notes verbatim after a
+/// line `%%`:
+/// ```text
+/// savan/1 9f3c0a1b2c3d4e5f
+/// a
+/// ~b(1)
+/// %%
+/// ...
+/// ```
+/// Lines starting with a single `%` before notes are read as notes too, one per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedRoute {
+    /// [Hash](program_hash) of program and arguments.
+    pub program: String,
+    /// Facets in order of activation.
+    pub route: Vec<String>,
+    /// Free-form notes, e.g., why facets were activated.
+    pub notes: Option<String>,
+}
+impl SavedRoute {
+    /// Saves **route** over program of **nav**.
+    pub fn new<B: SolverBackend>(
+        nav: &Navigator<B>,
+        route: &[String],
+        notes: Option<String>,
+    ) -> Self {
+        Self {
+            program: program_hash(&nav.program(), nav.args()),
+            route: route.to_vec(),
+            notes,
+        }
+    }
+
+    /// Returns saved route, after validating that **nav** navigates the saved program and knows
+    /// all atoms of the route.
+    pub fn restore<B: SolverBackend>(&self, nav: &Navigator<B>) -> Result<Vec<String>> {
+        if self.program != program_hash(&nav.program(), nav.args()) {
+            return Err(NavigatorError::InvalidInput(format!(
+                "route was saved for program {}",
+                self.program
+            )));
+        }
+        for facet in &self.route {
+            let atom = facet.trim().trim_start_matches('~').trim();
+            if nav.is_known(atom.to_owned()) != Some(true) {
+                return Err(NavigatorError::InvalidInput(format!("unknown atom {atom}")));
+            }
+        }
+
+        Ok(self.route.clone())
+    }
+
+    /// Returns text form.
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{HEADER} {}\n", self.program);
+        for facet in &self.route {
+            out.push_str(facet.trim());
+            out.push('\n');
+        }
+        if let Some(notes) = &self.notes {
+            out.push_str(NOTES);
+            out.push('\n');
+            out.push_str(notes);
+        }
+
+        out
+    }
+
+    /// Parses text form **text**.
+    pub fn from_text(text: &str) -> Result<Self> {
+        let (text, verbatim) = match text.split_once(&format!("\n{NOTES}\n")) {
+            Some((text, notes)) => (text, Some(notes.to_owned())),
+            None => match text.strip_suffix(&format!("\n{NOTES}")) {
+                Some(text) => (text, Some(String::new())),
+                None => (text, None),
+            },
+        };
+        let mut lines = text.lines();
+        let program = lines
+            .next()
+            .and_then(|l| l.trim().strip_prefix(HEADER))
+            .map(|h| h.trim().to_owned())
+            .filter(|h| !h.is_empty())
+            .ok_or_else(|| NavigatorError::InvalidInput(format!("expected '{HEADER} <hash>'")))?;
+
+        let mut route = vec![];
+        let mut notes: Option<String> = None;
+        for line in lines {
+            let line = line.trim();
+            match line.strip_prefix('%') {
+                Some(note) => {
+                    let note = note.strip_prefix(' ').unwrap_or(note);
+                    match notes.as_mut() {
+                        Some(notes) => {
+                            notes.push('\n');
+                            notes.push_str(note);
+                        }
+                        None => notes = Some(note.to_owned()),
+                    }
+                }
+                None if line.is_empty() => (),
+                None => route.push(line.to_owned()),
+            }
+        }
+
+        Ok(Self {
+            program,
+            route,
+            notes: verbatim.or(notes),
+        })
+    }
+
+    /// Returns JSON form.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "version": 1,
+            "program": self.program,
+            "route": self.route,
+            "notes": self.notes,
+        })
+        .to_string()
+    }
+
+    /// Parses JSON form **json**.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self> {
+        let invalid = |e: &str| NavigatorError::InvalidInput(format!("invalid route: {e}"));
+        let value =
+            serde_json::from_str::<serde_json::Value>(json).map_err(|e| invalid(&e.to_string()))?;
+        if value["version"] != 1 {
+            return Err(invalid("unsupported version"));
+        }
+        let program = value["program"]
+            .as_str()
+            .ok_or_else(|| invalid("expected program hash"))?
+            .to_owned();
+        let route = value["route"]
+            .as_array()
+            .ok_or_else(|| invalid("expected route"))?
+            .iter()
+            .map(|f| f.as_str().map(|f| f.to_owned()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| invalid("expected facets"))?;
+        let notes = match &value["notes"] {
+            serde_json::Value::Null => None,
+            notes => Some(
+                notes
+                    .as_str()
+                    .ok_or_else(|| invalid("expected notes"))?
+                    .to_owned(),
+            ),
+        };
+
+        Ok(Self {
+            program,
+            route,
+            notes,
+        })
+    }
+
+    /// Parses **input** in either form, telling them apart by a leading `{`.
+    #[cfg(feature = "json")]
+    pub fn parse(input: &str) -> Result<Self> {
+        match input.trim_start().starts_with('{') {
+            true => Self::from_json(input),
+            _ => Self::from_text(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::Memory;
+    use proptest::prelude::*;
+
+    fn toy() -> Result<Navigator<Memory>> {
+        let answer_sets = vec![vec!["a", "e"], vec!["b(1,\"x y\")", "e"]];
+        Navigator::with_backend(Memory::new(answer_sets), "")
+    }
+
+    #[test]
+    fn hash_programs() {
+        assert_eq!(program_hash("", &[]), "cbf29ce484222325");
+        assert_ne!(
+            program_hash("a.", &[]),
+            program_hash("a.", &["0".to_owned()])
+        );
+        assert_ne!(
+            program_hash("a.", &["0".to_owned(), "1".to_owned()]),
+            program_hash("a.", &["01".to_owned()])
+        );
+    }
+
+    #[test]
+    fn save_and_restore_text() -> Result<()> {
+        let nav = toy()?;
+        let route = vec!["~a".to_owned(), "b(1,\"x y\")".to_owned()];
+        let saved = SavedRoute::new(&nav, &route, Some("first\nsecond".to_owned()));
+
+        let text = saved.to_text();
+        assert_eq!(
+            text,
+            "savan/1 cbf29ce484222325\n~a\nb(1,\"x y\")\n%%\nfirst\nsecond"
+        );
+        let loaded = SavedRoute::from_text(&text)?;
+        assert_eq!(loaded, saved);
+        assert_eq!(loaded.restore(&nav)?, route);
+
+        for notes in ["  indented\n", "", "\n% not a facet\n%%\n"] {
+            let saved = SavedRoute::new(&nav, &route, Some(notes.to_owned()));
+            assert_eq!(SavedRoute::from_text(&saved.to_text())?, saved);
+        }
+
+        let bare = SavedRoute::from_text("savan/1 cbf29ce484222325\n\na\n")?;
+        assert_eq!(bare.notes, None);
+        assert_eq!(bare.restore(&nav)?, vec!["a".to_owned()]);
+
+        let legacy = SavedRoute::from_text("savan/1 cbf29ce484222325\na\n% first\n% second\n")?;
+        assert_eq!(legacy.notes, Some("first\nsecond".to_owned()));
+        assert_eq!(legacy.route, vec!["a".to_owned()]);
+
+        assert!(SavedRoute::from_text("a\n~b").is_err());
+        assert!(SavedRoute::from_text("savan/1\na").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_other_programs() -> Result<()> {
+        let nav = toy()?;
+        let mut saved = SavedRoute::new(&nav, &["a".to_owned()], None);
+        saved.program = program_hash("a.", &[]);
+        assert!(saved.restore(&nav).is_err());
+
+        let saved = SavedRoute::new(&nav, &["~c".to_owned()], None);
+        assert!(saved.restore(&nav).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn save_and_restore_json() -> Result<()> {
+        let nav = toy()?;
+        let route = vec!["a".to_owned(), "~e".to_owned()];
+        let saved = SavedRoute::new(&nav, &route, None);
+
+        let json = saved.to_json();
+        assert_eq!(SavedRoute::from_json(&json)?, saved);
+        assert_eq!(SavedRoute::parse(&json)?.restore(&nav)?, route);
+        assert_eq!(SavedRoute::parse(&saved.to_text())?, saved);
+
+        let noted = SavedRoute {
+            notes: Some("why \"a\"".to_owned()),
+            ..saved
+        };
+        assert_eq!(SavedRoute::parse(&noted.to_json())?, noted);
+
+        assert!(SavedRoute::from_json(r#"{"version":2,"program":"0","route":[]}"#).is_err());
+        assert!(SavedRoute::from_json(r#"{"version":1,"program":"0","route":[1]}"#).is_err());
+        assert!(SavedRoute::from_json("[]").is_err());
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_text(
+            program in "[0-9a-f]{16}",
+            route in prop::collection::vec("~?[a-z][a-z0-9_]{0,3}", 0..4),
+            notes in prop::option::of(any::<String>()),
+        ) {
+            let saved = SavedRoute { program, route, notes };
+            prop_assert_eq!(SavedRoute::from_text(&saved.to_text())?, saved);
+        }
+    }
+}
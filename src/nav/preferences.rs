@@ -0,0 +1,255 @@
+use super::backend::{Mode, Optimization, SolverBackend};
+use super::diagnosis::{core_of, corrections, shrink};
+use super::errors::Result;
+use super::facets::consequences;
+use super::Navigator;
+use std::collections::BTreeSet;
+
+/// Solutions under a route honouring as many preferences as possible.
+///
+/// Preferences act like weak constraints `:~ not f. [1@p]` for a preferred facet `f`, at a
+/// priority `p` above all others: only solutions violating the least number of preferences
+/// count, and among those, solutions are restricted according to
+/// [optimize](Navigator::optimize), e.g., to the ones of least cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preferred {
+    /// Solutions paired with the preferences they violate.
+    pub solutions: Vec<(Vec<String>, Vec<String>)>,
+    /// Atoms true in some solution, sorted.
+    pub brave: Vec<String>,
+    /// Atoms true in all solutions, sorted.
+    pub cautious: Vec<String>,
+    /// Preferences violated by all solutions, i.e., that could not be honoured.
+    pub unhonoured: Vec<String>,
+    /// Number of preferences each solution violates.
+    pub violations: usize,
+}
+
+/// Navigating along soft facets, i.e., preferences instead of hard assumptions.
+pub trait Preferences {
+    /// Returns [Preferred](Preferred) solutions under **route** given soft facets
+    /// **preferences**, or [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None),
+    /// if there are no solutions under **route** itself.
+    ///
+    /// Will return all such solutions, if **upper_bound** is
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None); consequences
+    /// are exact regardless. Preferences over unknown atoms are ignored. Which preferences to
+    /// violate is found from cores of all preferences, as corrections in
+    /// [diagnose](super::diagnosis::Diagnose::diagnose) are.
+    fn preferred<S: ToString, T: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        preferences: impl Iterator<Item = T>,
+        upper_bound: Option<usize>,
+    ) -> Result<Option<Preferred>>;
+}
+impl<B: SolverBackend> Preferences for Navigator<B> {
+    fn preferred<S: ToString, T: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        preferences: impl Iterator<Item = T>,
+        upper_bound: Option<usize>,
+    ) -> Result<Option<Preferred>> {
+        let route = self.route(route);
+        let preferences = self.facets(preferences);
+        if self.backend.core(&route)?.is_some() {
+            return Ok(None);
+        }
+
+        // solutions under route and all but k preferences violate exactly the k others, if k is
+        // the least size of corrections of preferences under route
+        let all = (0..preferences.len()).collect::<Vec<_>>();
+        let mut relaxations = match core_of(&mut self.backend, &preferences, &all, &route)? {
+            Some(core) => {
+                let core = shrink(&mut self.backend, &preferences, &route, core)?;
+                corrections(&mut self.backend, &preferences, &route, vec![core], true)?
+            }
+            None => vec![vec![]],
+        };
+        // preferences take precedence over costs, so among relaxations, only optimal ones count
+        if *self.optimization() == Optimization::Optimal {
+            let mut optima = vec![];
+            for dropped in &relaxations {
+                let assumptions = honouring(&route, &preferences, dropped);
+                optima.push(self.backend.optimum(&assumptions)?);
+            }
+            let best = optima.iter().min().cloned().flatten();
+            relaxations = relaxations
+                .into_iter()
+                .zip(optima)
+                .filter(|(_, optimum)| *optimum == best)
+                .map(|(dropped, _)| dropped)
+                .collect();
+        }
+
+        let names = |dropped: &[usize]| {
+            dropped
+                .iter()
+                .map(|i| preferences[*i].0.clone())
+                .collect::<Vec<_>>()
+        };
+        let mut solutions = vec![];
+        let (mut brave, mut cautious) = (BTreeSet::new(), None::<BTreeSet<String>>);
+        for dropped in &relaxations {
+            let assumptions = honouring(&route, &preferences, dropped);
+            if upper_bound != Some(solutions.len()) {
+                self.backend
+                    .solve(&assumptions, Mode::Models, false, |atoms| {
+                        let atoms = atoms.iter().map(|a| a.to_string()).collect();
+                        solutions.push((atoms, names(dropped)));
+                        upper_bound != Some(solutions.len())
+                    })?;
+            }
+
+            let strings = |xs: Vec<B::Atom>| xs.iter().map(|x| x.to_string()).collect();
+            if let Some(bcs) = consequences(self, &assumptions, Mode::Brave) {
+                brave.extend(strings(bcs));
+            }
+            if let Some(ccs) = consequences(self, &assumptions, Mode::Cautious) {
+                let ccs: BTreeSet<_> = strings(ccs);
+                cautious = Some(match cautious {
+                    Some(xs) => xs.intersection(&ccs).cloned().collect(),
+                    None => ccs,
+                });
+            }
+        }
+
+        let unhonoured = (0..preferences.len())
+            .filter(|i| relaxations.iter().all(|dropped| dropped.contains(i)))
+            .collect::<Vec<_>>();
+
+        Ok(Some(Preferred {
+            solutions,
+            brave: brave.into_iter().collect(),
+            cautious: cautious.unwrap_or_default().into_iter().collect(),
+            unhonoured: names(&unhonoured),
+            violations: relaxations.first().map(|d| d.len()).unwrap_or_default(),
+        }))
+    }
+}
+
+/// Returns **route** extended by the literals of all **preferences** except those at indices
+/// **dropped**.
+fn honouring<L: Copy>(route: &[L], preferences: &[(String, L)], dropped: &[usize]) -> Vec<L> {
+    let mut assumptions = route.to_vec();
+    assumptions.extend(
+        preferences
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !dropped.contains(i))
+            .map(|(_, (_, l))| *l),
+    );
+
+    assumptions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::fixtures::{strings, toy};
+    use crate::nav::backend::Memory;
+    use crate::nav::errors::NavigatorError;
+
+    #[test]
+    fn honour_preferences() -> Result<()> {
+        let mut nav = toy()?;
+        let none = std::iter::empty::<String>;
+
+        let preferred = nav
+            .preferred(none(), ["c", "d", "~a"].iter(), None)?
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(preferred.violations, 1);
+        assert_eq!(
+            preferred.solutions,
+            vec![
+                (strings(&["b", "d", "e"]), strings(&["c"])),
+                (strings(&["b", "c", "e"]), strings(&["d"])),
+            ]
+        );
+        assert_eq!(preferred.brave, strings(&["b", "c", "d", "e"]));
+        assert_eq!(preferred.cautious, strings(&["b", "e"]));
+        assert!(preferred.unhonoured.is_empty());
+
+        let preferred = nav
+            .preferred(["~b"].iter(), ["c", "e", "f"].iter(), Some(1))?
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            preferred.solutions,
+            vec![(strings(&["a", "e"]), strings(&["c"]))]
+        );
+        assert_eq!(preferred.unhonoured, strings(&["c"]));
+
+        let preferred = nav
+            .preferred(none(), ["b", "~c"].iter(), None)?
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(preferred.violations, 0);
+        assert_eq!(preferred.cautious, strings(&["b", "d", "e"]));
+
+        assert_eq!(nav.preferred(["a", "b"].iter(), ["c"].iter(), None)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn honour_many_preferences() -> Result<()> {
+        let atoms = (0..30).map(|i| format!("x{i}")).collect::<Vec<_>>();
+        let answer_sets = atoms.iter().map(|a| vec![a.as_str(), "y"]);
+        let mut nav = Navigator::with_backend(Memory::new(answer_sets), "")?;
+
+        let preferred = nav
+            .preferred(["~x0"].iter(), atoms.iter(), None)?
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(preferred.violations, 29);
+        assert_eq!(preferred.solutions.len(), 29);
+        assert_eq!(preferred.unhonoured, strings(&["x0"]));
+        assert_eq!(preferred.cautious, strings(&["y"]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn honour_preferences_before_costs() -> Result<()> {
+        let answer_sets = vec![
+            (vec!["a", "e"], vec![2]),
+            (vec!["b", "c", "e"], vec![1]),
+            (vec!["b", "d", "e"], vec![0]),
+        ];
+        let mut nav = Navigator::with_backend(Memory::with_costs(answer_sets), "")?;
+        let none = std::iter::empty::<String>;
+
+        let preferred = nav
+            .preferred(none(), ["a", "c"].iter(), None)?
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(preferred.solutions.len(), 2);
+        assert!(preferred.unhonoured.is_empty());
+
+        nav.optimize(Optimization::Optimal)?;
+        let preferred = nav
+            .preferred(none(), ["a", "c"].iter(), None)?
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            preferred.solutions,
+            vec![(strings(&["b", "c", "e"]), strings(&["a"]))]
+        );
+        assert_eq!(preferred.unhonoured, strings(&["a"]));
+        assert_eq!(preferred.cautious, strings(&["b", "c", "e"]));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn honour_preferences_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let preferred = nav
+            .preferred(["~b"].iter(), ["c", "e"].iter(), None)?
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(preferred.violations, 1);
+        assert_eq!(preferred.solutions.len(), 1);
+        assert_eq!(preferred.unhonoured, strings(&["c"]));
+        assert_eq!(preferred.cautious, strings(&["a", "e"]));
+
+        Ok(())
+    }
+}
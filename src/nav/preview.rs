@@ -0,0 +1,217 @@
+use super::backend::{Mode, SolverBackend};
+use super::facets::{consequences, consequences_projecting};
+use super::Navigator;
+use std::collections::BTreeSet;
+
+/// Effect of activating a facet under a route.
+///
+/// Atoms are sorted. Under routes without solutions, all atoms count as false.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preview {
+    /// Previewed facet.
+    pub facet: String,
+    /// Atoms other than the one of the facet that become true in all solutions.
+    pub forced_true: Vec<String>,
+    /// Atoms other than the one of the facet that become false in all solutions.
+    pub forced_false: Vec<String>,
+    /// Facet-inducing atoms under the extended route, i.e., atoms that stay open.
+    pub open: Vec<String>,
+}
+
+/// Previewing facets before activating them.
+///
+/// If **projecting** is set, only shown atoms are considered.
+pub trait Lookahead {
+    /// Returns [Preview](Preview) of activating **facet** under **route**, or
+    /// [None](https://doc.rust-lang.org/std/option/enum.Option.html#variant.None), if **facet**
+    /// is over an unknown atom or solving fails.
+    fn preview<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        facet: &str,
+        projecting: bool,
+    ) -> Option<Preview>;
+    /// Returns previews of all facets under **route**, ordered like
+    /// [facet_weights](super::weights::facet_weights).
+    ///
+    /// Consequences under **route** are computed once for all facets.
+    fn preview_all<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        projecting: bool,
+    ) -> Option<Vec<Preview>>;
+}
+impl<B: SolverBackend> Lookahead for Navigator<B> {
+    fn preview<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        facet: &str,
+        projecting: bool,
+    ) -> Option<Preview> {
+        let route = self.route(route);
+        let before = brave_and_cautious(self, &route, projecting)?;
+
+        preview(self, &route, &before, facet.trim(), projecting)
+    }
+
+    fn preview_all<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        projecting: bool,
+    ) -> Option<Vec<Preview>> {
+        let route = self.route(route);
+        let before = brave_and_cautious(self, &route, projecting)?;
+        let (bcs, ccs) = &before;
+
+        let mut previews = vec![];
+        for atom in bcs.difference(ccs) {
+            for facet in [atom.clone(), format!("~{atom}")] {
+                previews.push(preview(self, &route, &before, &facet, projecting)?);
+            }
+        }
+
+        Some(previews)
+    }
+}
+
+/// Returns brave and cautious consequences under **route** as strings.
+pub(crate) fn brave_and_cautious<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    projecting: bool,
+) -> Option<(BTreeSet<String>, BTreeSet<String>)> {
+    let mut consequences = |mode| {
+        match projecting {
+            true => consequences_projecting(nav, route, mode),
+            _ => consequences(nav, route, mode),
+        }
+        .map(|xs| xs.iter().map(|x| x.to_string()).collect::<BTreeSet<_>>())
+    };
+    let bcs = consequences(Mode::Brave)?;
+    let ccs = consequences(Mode::Cautious)?;
+
+    Some((bcs, ccs))
+}
+
+/// Returns preview of **facet** under **route**, given consequences **before** activating it.
+pub(crate) fn preview<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    before: &(BTreeSet<String>, BTreeSet<String>),
+    facet: &str,
+    projecting: bool,
+) -> Option<Preview> {
+    let literal = nav.expression_to_literal(facet)?;
+    let atom = nav
+        .backend
+        .parse_atom(facet.trim_start_matches('~').trim())?
+        .to_string();
+    let extended = [route, &[literal]].concat();
+    let (bcs, ccs) = brave_and_cautious(nav, &extended, projecting)?;
+
+    let others = |xs: BTreeSet<&String>| {
+        xs.into_iter()
+            .filter(|a| **a != atom)
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let (bcs_before, ccs_before) = before;
+
+    Some(Preview {
+        facet: facet.to_owned(),
+        forced_true: others(ccs.difference(ccs_before).collect()),
+        forced_false: others(bcs_before.difference(&bcs).collect()),
+        open: others(bcs.difference(&ccs).collect()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::fixtures::{strings, toy};
+    use crate::nav::errors::{NavigatorError, Result};
+
+    #[test]
+    fn preview_facets() -> Result<()> {
+        let mut nav = toy()?;
+        let none = std::iter::empty::<String>();
+
+        assert_eq!(
+            nav.preview(none, "c", false),
+            Some(Preview {
+                facet: "c".to_owned(),
+                forced_true: strings(&["b"]),
+                forced_false: strings(&["a", "d"]),
+                open: vec![],
+            })
+        );
+        assert_eq!(
+            nav.preview(["~c"].iter(), " ~a ", false),
+            Some(Preview {
+                facet: "~a".to_owned(),
+                forced_true: strings(&["b", "d"]),
+                forced_false: vec![],
+                open: vec![],
+            })
+        );
+        assert_eq!(
+            nav.preview(["a"].iter(), "b", false),
+            Some(Preview {
+                facet: "b".to_owned(),
+                forced_true: vec![],
+                forced_false: strings(&["a", "e"]),
+                open: vec![],
+            })
+        );
+        assert_eq!(nav.preview(["a"].iter(), "f", false), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn preview_all_facets() -> Result<()> {
+        let mut nav = toy()?;
+
+        let previews = nav
+            .preview_all(["b"].iter(), false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            previews
+                .iter()
+                .map(|p| p.facet.as_str())
+                .collect::<Vec<_>>(),
+            vec!["c", "~c", "d", "~d"]
+        );
+        assert_eq!(previews[0].forced_false, strings(&["d"]));
+        assert_eq!(previews[1].forced_true, strings(&["d"]));
+
+        let previews = nav
+            .preview_all(std::iter::empty::<String>(), false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(previews.len(), 8);
+        assert_eq!(previews[2].facet, "b");
+        assert_eq!(previews[2].open, strings(&["c", "d"]));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn preview_facets_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let preview = nav
+            .preview(std::iter::empty::<String>(), "~a", false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(preview.forced_true, strings(&["b"]));
+        assert!(preview.forced_false.is_empty());
+        assert_eq!(preview.open, strings(&["c", "d"]));
+
+        let previews = nav
+            .preview_all(["b"].iter(), false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(previews.len(), 4);
+
+        Ok(())
+    }
+}
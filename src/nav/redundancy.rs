@@ -0,0 +1,231 @@
+use super::backend::SolverBackend;
+use super::Navigator;
+
+/// Detecting and removing facets of a route that are implied by the rest of it.
+///
+/// A facet is implied by a route, if the route extended by the complement of the facet has no
+/// solutions, e.g., `~a` is implied, if `a` is false in all solutions under the route. This holds
+/// for facets over hidden atoms alike. Facets over unknown atoms are ignored, and under routes
+/// without solutions, no facet is considered redundant.
+pub trait Redundancy {
+    /// Returns facets of **route** implied by the remaining facets of **route**.
+    fn redundant_facets<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Option<Vec<String>>;
+    /// Returns subsequence of **route** with the same solutions, in which no facet is implied by
+    /// the others.
+    ///
+    /// Facets are dropped greedily in order of **route**, so for facets implying each other, the
+    /// later one is kept.
+    fn minimize_route<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Option<Vec<String>>;
+    /// Returns facets of **route** over known atoms in canonical form, i.e., deduplicated and
+    /// sorted by atom, with `a` before `~a`, so routes differing only in order or formatting
+    /// coincide.
+    fn canonical_route<S: ToString>(&self, route: impl Iterator<Item = S>) -> Vec<String>;
+}
+impl<B: SolverBackend> Redundancy for Navigator<B> {
+    fn redundant_facets<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Option<Vec<String>> {
+        let facets = self.facets(route);
+        let all = facets.iter().map(|(_, l)| *l).collect::<Vec<_>>();
+        if self.backend.core(&all).ok()?.is_some() {
+            return Some(vec![]);
+        }
+
+        let mut redundant = vec![];
+        for (i, (facet, literal)) in facets.iter().enumerate() {
+            let mut rest = all.clone();
+            rest.remove(i);
+            if self.implied(*literal, &rest)? {
+                redundant.push(facet.clone());
+            }
+        }
+
+        Some(redundant)
+    }
+
+    fn minimize_route<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+    ) -> Option<Vec<String>> {
+        let mut facets = self.facets(route);
+        let all = facets.iter().map(|(_, l)| *l).collect::<Vec<_>>();
+        if self.backend.core(&all).ok()?.is_some() {
+            return Some(facets.into_iter().map(|(f, _)| f).collect());
+        }
+
+        let mut i = 0;
+        while i < facets.len() {
+            let rest = facets
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, (_, l))| *l)
+                .collect::<Vec<_>>();
+            match self.implied(facets[i].1, &rest)? {
+                true => {
+                    facets.remove(i);
+                }
+                _ => i += 1,
+            }
+        }
+
+        Some(facets.into_iter().map(|(f, _)| f).collect())
+    }
+
+    fn canonical_route<S: ToString>(&self, route: impl Iterator<Item = S>) -> Vec<String> {
+        let mut facets = self
+            .facets(route)
+            .into_iter()
+            .filter_map(|(facet, _)| {
+                let (positive, atom) = split(&facet);
+                let atom = self.backend.parse_atom(atom)?.to_string();
+                Some((atom, !positive))
+            })
+            .collect::<Vec<_>>();
+        facets.sort();
+        facets.dedup();
+
+        facets
+            .into_iter()
+            .map(|(atom, negative)| match negative {
+                true => format!("~{atom}"),
+                _ => atom,
+            })
+            .collect()
+    }
+}
+impl<B: SolverBackend> Navigator<B> {
+    /// Checks whether facet of **literal** is implied by route of literals **rest**.
+    fn implied(&mut self, literal: B::Literal, rest: &[B::Literal]) -> Option<bool> {
+        let complement = self.backend.negate(literal);
+        let core = self.backend.core(&[rest, &[complement]].concat()).ok()?;
+
+        Some(core.is_some())
+    }
+}
+
+/// Splits **facet** into its sign and atom.
+fn split(facet: &str) -> (bool, &str) {
+    match facet.trim().strip_prefix('~') {
+        Some(atom) => (false, atom.trim()),
+        None => (true, facet.trim()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::fixtures::toy;
+    use crate::nav::errors::{NavigatorError, Result};
+
+    #[test]
+    fn detect_redundant_facets() -> Result<()> {
+        let mut nav = toy()?;
+
+        let redundant = nav
+            .redundant_facets(["b", "~a", "c", "e", "f"].iter())
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(redundant, vec!["b", "~a", "e"]);
+        assert_eq!(
+            nav.redundant_facets(["a", "~c"].iter()),
+            Some(vec!["~c".to_owned()])
+        );
+        assert_eq!(nav.redundant_facets(["a", "b"].iter()), Some(vec![]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn minimize_routes() -> Result<()> {
+        let mut nav = toy()?;
+
+        assert_eq!(
+            nav.minimize_route(["b", "~a", "c", "e"].iter()),
+            Some(vec!["c".to_owned()])
+        );
+        assert_eq!(
+            nav.minimize_route(["~a", "b", "~d"].iter()),
+            Some(vec!["b".to_owned(), "~d".to_owned()])
+        );
+        assert_eq!(
+            nav.minimize_route(["a", "b", "a"].iter()),
+            Some(vec!["a".to_owned(), "b".to_owned()])
+        );
+
+        let route = ["~a", "~d", "e"];
+        let minimal = nav
+            .minimize_route(route.iter())
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            nav.solutions(None, route.iter())?,
+            nav.solutions(None, minimal.iter())?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonicalize_routes() -> Result<()> {
+        let nav = toy()?;
+
+        assert_eq!(
+            nav.canonical_route(["e", "~a", " b ", "b", "f", "~ a"].iter()),
+            vec!["~a", "b", "e"]
+        );
+        assert_eq!(
+            nav.canonical_route(["~c", "c"].iter()),
+            nav.canonical_route(["c", "~c"].iter())
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn minimize_routes_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        assert_eq!(
+            nav.redundant_facets(["b", "~a", "c", "e"].iter()),
+            Some(vec!["b".to_owned(), "~a".to_owned(), "e".to_owned()])
+        );
+        assert_eq!(
+            nav.minimize_route(["b", "~a", "c", "e"].iter()),
+            Some(vec!["c".to_owned()])
+        );
+        assert_eq!(
+            nav.canonical_route(["e", "~a", "b"].iter()),
+            vec!["~a", "b", "e"]
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn keep_facets_over_hidden_atoms() -> Result<()> {
+        let mut nav = Navigator::new(
+            "a;b. h;g :- b. #show a/0. #show b/0.",
+            vec!["0".to_string()],
+        )?;
+
+        assert_eq!(nav.redundant_facets(["b", "~h"].iter()), Some(vec![]));
+        assert_eq!(
+            nav.minimize_route(["b", "~h"].iter()),
+            Some(vec!["b".to_owned(), "~h".to_owned()])
+        );
+        assert_eq!(
+            nav.redundant_facets(["a", "~h"].iter()),
+            Some(vec!["~h".to_owned()])
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,400 @@
+use super::weights::Weight;
+use std::collections::BTreeSet;
+
+/// Reference implementation of navigation over an explicit list of answer sets.
+///
+/// Everything is computed by filtering and combining answer sets directly, without any solver,
+/// so results serve as ground truth for [Navigator](super::Navigator) in differential tests.
+///
+/// Routes are facets such as `a` or `~a`; `a` holds in answer sets containing `a`, and `~a` in
+/// those not containing `a`. Atoms are compared verbatim.
+#[derive(Debug, Clone, Default)]
+pub struct Reference {
+    answer_sets: Vec<BTreeSet<String>>,
+}
+impl Reference {
+    /// Constructs reference over **answer_sets**, dropping duplicates.
+    pub fn new<A: ToString>(answer_sets: impl IntoIterator<Item = Vec<A>>) -> Self {
+        let mut unique = vec![];
+        for xs in answer_sets {
+            let xs = xs.iter().map(|x| x.to_string()).collect::<BTreeSet<_>>();
+            if !unique.contains(&xs) {
+                unique.push(xs);
+            }
+        }
+
+        Self {
+            answer_sets: unique,
+        }
+    }
+
+    /// Returns answer sets satisfying every facet of **route**.
+    pub fn solutions<S: AsRef<str>>(&self, route: &[S]) -> Vec<&BTreeSet<String>> {
+        self.answer_sets
+            .iter()
+            .filter(|xs| {
+                route
+                    .iter()
+                    .all(|f| match f.as_ref().trim().strip_prefix('~') {
+                        Some(atom) => !xs.contains(atom.trim()),
+                        None => xs.contains(f.as_ref().trim()),
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns atoms true in some answer set under **route**.
+    pub fn brave_consequences<S: AsRef<str>>(&self, route: &[S]) -> BTreeSet<String> {
+        self.solutions(route)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns atoms true in all answer sets under **route**; empty if there are none.
+    pub fn cautious_consequences<S: AsRef<str>>(&self, route: &[S]) -> BTreeSet<String> {
+        let mut solutions = self.solutions(route).into_iter();
+        match solutions.next() {
+            Some(first) => solutions.fold(first.clone(), |acc, xs| {
+                acc.intersection(xs).cloned().collect()
+            }),
+            None => BTreeSet::new(),
+        }
+    }
+
+    /// Returns atoms true in some but not all answer sets under **route**.
+    pub fn facet_inducing_atoms<S: AsRef<str>>(&self, route: &[S]) -> BTreeSet<String> {
+        let ccs = self.cautious_consequences(route);
+        self.brave_consequences(route)
+            .into_iter()
+            .filter(|a| !ccs.contains(a))
+            .collect()
+    }
+
+    /// Returns count of **weight** under **route**.
+    ///
+    /// Facet counting counts facets, i.e., twice the number of facet-inducing atoms, and
+    /// answer set counting counts answer sets.
+    pub fn count<S: AsRef<str>>(&self, weight: &Weight, route: &[S]) -> usize {
+        match weight {
+            Weight::FacetCounting => 2 * self.facet_inducing_atoms(route).len(),
+            Weight::AnswerSetCounting => self.solutions(route).len(),
+        }
+    }
+
+    /// Returns weights of facets under **route**, ordered like
+    /// [facet_weights](super::weights::facet_weights).
+    pub fn facet_weights<S: AsRef<str>>(
+        &self,
+        weight: &Weight,
+        route: &[S],
+    ) -> Vec<(String, usize)> {
+        let total = self.count(weight, route);
+        let route = route
+            .iter()
+            .map(|f| f.as_ref().to_owned())
+            .collect::<Vec<_>>();
+
+        let mut ws = vec![];
+        for atom in self.facet_inducing_atoms(&route) {
+            for facet in [atom.clone(), format!("~{atom}")] {
+                let mut extended = route.clone();
+                extended.push(facet.clone());
+                ws.push((facet, total.saturating_sub(self.count(weight, &extended))));
+            }
+        }
+
+        ws
+    }
+
+    /// Returns atoms in **target_atoms** that are true in some answer set under **route**, i.e.,
+    /// those covered by [sieve_quiet](super::soe::Collect::sieve_quiet).
+    pub fn sieve<S: AsRef<str>>(&self, route: &[S], target_atoms: &[String]) -> BTreeSet<String> {
+        let bcs = self.brave_consequences(route);
+        target_atoms
+            .iter()
+            .filter(|a| bcs.contains(*a))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::{Memory, SolverBackend};
+    use crate::nav::errors::{NavigatorError, Result};
+    use crate::nav::facets::Facets;
+    use crate::nav::soe::Collect;
+    use crate::nav::weights::{count, facet_weights};
+    use crate::nav::Navigator;
+    use proptest::prelude::*;
+    use std::fmt;
+
+    /// Answer sets of `a;b. c;d :- b. e.`.
+    fn toy() -> Reference {
+        Reference::new(vec![
+            vec!["a", "e"],
+            vec!["b", "c", "e"],
+            vec!["b", "d", "e"],
+        ])
+    }
+
+    fn set(atoms: &[&str]) -> BTreeSet<String> {
+        atoms.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn reference_toy() {
+        let r = toy();
+        let none: [&str; 0] = [];
+
+        assert_eq!(r.solutions(&["~c"]).len(), 2);
+        assert_eq!(r.brave_consequences(&["b"]), set(&["b", "c", "d", "e"]));
+        assert_eq!(r.cautious_consequences(&["b"]), set(&["b", "e"]));
+        assert_eq!(r.cautious_consequences(&["a", "b"]), set(&[]));
+        assert_eq!(r.facet_inducing_atoms(&none), set(&["a", "b", "c", "d"]));
+
+        assert_eq!(r.count(&Weight::FacetCounting, &none), 8);
+        assert_eq!(r.count(&Weight::FacetCounting, &["~c"]), 6);
+        assert_eq!(r.count(&Weight::AnswerSetCounting, &["~a"]), 2);
+        assert_eq!(
+            r.facet_weights(&Weight::AnswerSetCounting, &["b"]),
+            vec![
+                ("c".to_owned(), 1),
+                ("~c".to_owned(), 1),
+                ("d".to_owned(), 1),
+                ("~d".to_owned(), 1)
+            ]
+        );
+
+        let targets = ["a".to_owned(), "c".to_owned(), "f".to_owned()];
+        assert_eq!(r.sieve(&["~a"], &targets), set(&["c"]));
+    }
+
+    /// Checks **nav** against **reference** under **route**.
+    fn agree<B: SolverBackend>(
+        nav: &mut Navigator<B>,
+        reference: &Reference,
+        route: &[String],
+    ) -> Result<()> {
+        let strings = |xs: Vec<B::Atom>| xs.iter().map(|x| x.to_string()).collect::<BTreeSet<_>>();
+
+        assert_eq!(
+            nav.solutions(None, route.iter())?.len(),
+            reference.solutions(route).len()
+        );
+        assert_eq!(
+            nav.brave_consequences(route.iter()).map(strings),
+            Some(reference.brave_consequences(route))
+        );
+        assert_eq!(
+            nav.cautious_consequences(route.iter()).map(strings),
+            Some(reference.cautious_consequences(route))
+        );
+        let fs = nav
+            .facet_inducing_atoms(route.iter())
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            strings(fs.into_iter().collect()),
+            reference.facet_inducing_atoms(route)
+        );
+
+        for mut w in [Weight::FacetCounting, Weight::AnswerSetCounting] {
+            assert_eq!(
+                count(&mut w, nav, route.iter()),
+                Some(reference.count(&w, route))
+            );
+            assert_eq!(
+                facet_weights(&mut w, nav, route),
+                Some(reference.facet_weights(&w, route))
+            );
+        }
+
+        let targets = nav.atoms().collect::<Vec<_>>();
+        let covered = nav
+            .sieve_quiet(route.iter(), &targets, false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(
+            covered.into_iter().collect::<BTreeSet<_>>(),
+            reference.sieve(route, &targets)
+        );
+
+        Ok(())
+    }
+
+    /// Returns route of **facets** over atoms known to **nav**.
+    fn route_of<B: SolverBackend>(nav: &Navigator<B>, facets: &[(usize, bool)]) -> Vec<String> {
+        facets
+            .iter()
+            .map(|(i, positive)| (format!("p{i}"), positive))
+            .filter(|(atom, _)| nav.is_known(atom.clone()) == Some(true))
+            .map(|(atom, positive)| match positive {
+                true => atom,
+                _ => format!("~{atom}"),
+            })
+            .collect()
+    }
+
+    const ATOMS: usize = 4;
+
+    /// Rule of a small propositional program over atoms `p0`, ..., `p3`.
+    #[derive(Debug, Clone)]
+    enum Rule {
+        /// `{h} :- body.`
+        Choice(usize, Vec<usize>, Vec<usize>),
+        /// `h :- body.`
+        Normal(usize, Vec<usize>, Vec<usize>),
+        /// `:- body.`
+        Constraint(Vec<usize>, Vec<usize>),
+    }
+    impl Rule {
+        fn body(&self) -> (&[usize], &[usize]) {
+            match self {
+                Self::Choice(_, pos, neg)
+                | Self::Normal(_, pos, neg)
+                | Self::Constraint(pos, neg) => (pos, neg),
+            }
+        }
+    }
+    impl fmt::Display for Rule {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let (pos, neg) = self.body();
+            let body = pos
+                .iter()
+                .map(|i| format!("p{i}"))
+                .chain(neg.iter().map(|i| format!("not p{i}")))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let head = match self {
+                Self::Choice(h, _, _) => format!("{{p{h}}}"),
+                Self::Normal(h, _, _) => format!("p{h}"),
+                Self::Constraint(_, _) => String::new(),
+            };
+            match (head.is_empty(), body.is_empty()) {
+                (_, true) => write!(f, "{head}."),
+                (true, _) => write!(f, ":- {body}."),
+                _ => write!(f, "{head} :- {body}."),
+            }
+        }
+    }
+
+    /// Returns answer sets of **rules** by checking every interpretation against the least model
+    /// of its reduct.
+    fn answer_sets(rules: &[Rule]) -> Vec<Vec<String>> {
+        let mut answer_sets = vec![];
+        for bits in 0..1usize << ATOMS {
+            let m = |i: &usize| bits & (1 << i) != 0;
+
+            let mut least = 0usize;
+            loop {
+                let before = least;
+                for rule in rules {
+                    let (pos, neg) = rule.body();
+                    let fires = pos.iter().all(|i| least & (1 << i) != 0) && !neg.iter().any(m);
+                    match rule {
+                        Rule::Normal(h, _, _) if fires => least |= 1 << h,
+                        Rule::Choice(h, _, _) if fires && m(h) => least |= 1 << h,
+                        _ => (),
+                    }
+                }
+                if least == before {
+                    break;
+                }
+            }
+
+            let violated = rules.iter().any(|rule| match rule {
+                Rule::Constraint(pos, neg) => pos.iter().all(m) && !neg.iter().any(m),
+                _ => false,
+            });
+            if least == bits && !violated {
+                answer_sets.push((0..ATOMS).filter(m).map(|i| format!("p{i}")).collect());
+            }
+        }
+
+        answer_sets
+    }
+
+    #[cfg(feature = "clingo")]
+    fn arb_body() -> impl Strategy<Value = (Vec<usize>, Vec<usize>)> {
+        (
+            prop::collection::vec(0..ATOMS, 0..2),
+            prop::collection::vec(0..ATOMS, 0..2),
+        )
+    }
+
+    #[cfg(feature = "clingo")]
+    fn arb_rule() -> impl Strategy<Value = Rule> {
+        prop_oneof![
+            3 => (0..ATOMS, arb_body()).prop_map(|(h, (pos, neg))| Rule::Choice(h, pos, neg)),
+            2 => (0..ATOMS, arb_body()).prop_map(|(h, (pos, neg))| Rule::Normal(h, pos, neg)),
+            1 => (
+                prop::collection::vec(0..ATOMS, 1..3),
+                prop::collection::vec(0..ATOMS, 0..2),
+            )
+                .prop_map(|(pos, neg)| Rule::Constraint(pos, neg)),
+        ]
+    }
+
+    fn arb_route() -> impl Strategy<Value = Vec<(usize, bool)>> {
+        prop::collection::vec((0..ATOMS, any::<bool>()), 0..3)
+    }
+
+    proptest! {
+        #[test]
+        fn memory_agrees_with_reference(
+            answer_sets in prop::collection::vec(
+                prop::collection::btree_set(0..ATOMS, 0..ATOMS),
+                0..6,
+            ),
+            facets in arb_route(),
+        ) {
+            let answer_sets = answer_sets
+                .iter()
+                .map(|xs| xs.iter().map(|i| format!("p{i}")).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let reference = Reference::new(answer_sets.clone());
+            let mut nav = Navigator::with_backend(Memory::new(answer_sets), "").unwrap();
+
+            let route = route_of(&nav, &facets);
+            agree(&mut nav, &reference, &route).unwrap();
+        }
+
+        #[cfg(feature = "clingo")]
+        #[test]
+        fn clingo_agrees_with_reference(
+            rules in prop::collection::vec(arb_rule(), 1..6),
+            facets in arb_route(),
+        ) {
+            let program = rules.iter().map(|r| r.to_string()).collect::<Vec<_>>().join("\n");
+            let reference = Reference::new(answer_sets(&rules));
+            let mut nav = Navigator::new(program, vec!["0".to_owned()]).unwrap();
+
+            let route = route_of(&nav, &facets);
+            agree(&mut nav, &reference, &route).unwrap();
+        }
+    }
+
+    #[test]
+    fn brute_force_answer_sets() {
+        // {p0}. p1 :- not p0. :- p0, p1.
+        let rules = [
+            Rule::Choice(0, vec![], vec![]),
+            Rule::Normal(1, vec![], vec![0]),
+            Rule::Constraint(vec![0, 1], vec![]),
+        ];
+        assert_eq!(
+            rules.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            vec!["{p0}.", "p1 :- not p0.", ":- p0, p1."]
+        );
+        assert_eq!(answer_sets(&rules), vec![vec!["p0"], vec!["p1"]]);
+
+        // p0 :- p1. p1 :- p0.
+        let rules = [
+            Rule::Normal(0, vec![1], vec![]),
+            Rule::Normal(1, vec![0], vec![]),
+        ];
+        assert_eq!(answer_sets(&rules), vec![Vec::<String>::new()]);
+    }
+}
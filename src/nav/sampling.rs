@@ -0,0 +1,227 @@
+use super::backend::{Mode, SolverBackend};
+use super::errors::{NavigatorError, Result};
+use super::preview::brave_and_cautious;
+use super::utils::Rng;
+use super::Navigator;
+use std::collections::HashMap;
+
+/// Number of solutions up to which both sides of a facet are counted exactly.
+const PIVOT: usize = 64;
+
+/// Drawing random solutions, independently of the order in which the solver finds them.
+///
+/// A sample is drawn by activating facets one at a time: a random facet-inducing atom is chosen,
+/// and then activated or excluded with probability proportional to the number of solutions it
+/// leaves, each counted up to 64. While both counts stay below 64, these probabilities multiply
+/// to one over the number of solutions, so samples are exactly uniform. Beyond that, counts are
+/// capped, and sides with at least 64 solutions are treated alike regardless of their actual
+/// size: each such step may skew the odds by the ratio of actual counts, so the bias is not
+/// bounded, and large spaces of solutions may be sampled far from uniformly.
+///
+/// If **projecting** is set, solutions projected onto shown atoms are sampled. Samples are
+/// reproducible from **seed**.
+pub trait Sample {
+    /// Returns **n** solutions under **route** drawn independently, or none, if there are no
+    /// solutions.
+    fn samples<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        n: usize,
+        projecting: bool,
+        seed: u64,
+    ) -> Result<Vec<Vec<String>>>;
+    /// Returns for each atom in **target_atoms** its relative frequency among **n** samples
+    /// under **route**, i.e., an estimate of the share of solutions it is true in, as far as
+    /// samples are uniform.
+    fn frequencies<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        n: usize,
+        projecting: bool,
+        seed: u64,
+    ) -> Result<Vec<(String, f64)>>;
+}
+impl<B: SolverBackend> Sample for Navigator<B> {
+    fn samples<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        n: usize,
+        projecting: bool,
+        seed: u64,
+    ) -> Result<Vec<Vec<String>>> {
+        let route = self.route(route);
+        if count(self, &route, projecting, 1)? == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut rng = Rng::new(seed);
+        let mut samples = vec![];
+        for _ in 0..n {
+            samples.push(sample(self, &route, projecting, &mut rng)?);
+        }
+
+        Ok(samples)
+    }
+
+    fn frequencies<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        n: usize,
+        projecting: bool,
+        seed: u64,
+    ) -> Result<Vec<(String, f64)>> {
+        let mut targets = HashMap::new();
+        for atom in target_atoms {
+            let parsed = self
+                .backend
+                .parse_atom(atom)
+                .ok_or_else(|| NavigatorError::InvalidInput(atom.clone()))?;
+            targets.insert(parsed.to_string(), 0usize);
+        }
+
+        let samples = self.samples(route, n, projecting, seed)?;
+        for atom in samples.iter().flatten() {
+            if let Some(count) = targets.get_mut(atom) {
+                *count += 1;
+            }
+        }
+
+        let size = samples.len().max(1) as f64;
+        Ok(target_atoms
+            .iter()
+            .filter_map(|atom| {
+                let parsed = self.backend.parse_atom(atom)?.to_string();
+                Some((atom.clone(), targets[&parsed] as f64 / size))
+            })
+            .collect())
+    }
+}
+
+/// Returns a random solution under satisfiable **route**.
+fn sample<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    projecting: bool,
+    rng: &mut Rng,
+) -> Result<Vec<String>> {
+    let mut route = route.to_vec();
+    loop {
+        let (bcs, ccs) = brave_and_cautious(nav, &route, projecting).ok_or(NavigatorError::None)?;
+        let facets = bcs.difference(&ccs).collect::<Vec<_>>();
+        if facets.is_empty() {
+            break;
+        }
+
+        let atom = facets[rng.below(facets.len())];
+        let positive = nav
+            .expression_to_literal(atom)
+            .ok_or_else(|| NavigatorError::InvalidInput(atom.clone()))?;
+        let negative = nav.backend.negate(positive);
+        let with = count(
+            nav,
+            &[route.as_slice(), &[positive]].concat(),
+            projecting,
+            PIVOT,
+        )?;
+        let without = count(
+            nav,
+            &[route.as_slice(), &[negative]].concat(),
+            projecting,
+            PIVOT,
+        )?;
+        route.push(match rng.below(with + without) < with {
+            true => positive,
+            _ => negative,
+        });
+    }
+
+    let mut solution = vec![];
+    nav.backend
+        .solve(&route, Mode::Models, projecting, |atoms| {
+            solution = atoms.iter().map(|a| a.to_string()).collect();
+            false
+        })?;
+
+    Ok(solution)
+}
+
+/// Returns number of solutions under **route**, counted up to **limit**.
+fn count<B: SolverBackend>(
+    nav: &mut Navigator<B>,
+    route: &[B::Literal],
+    projecting: bool,
+    limit: usize,
+) -> Result<usize> {
+    let mut n = 0;
+    nav.backend.solve(route, Mode::Models, projecting, |_| {
+        n += 1;
+        n < limit
+    })?;
+
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav::backend::fixtures::toy;
+
+    #[test]
+    fn sample_uniformly() -> Result<()> {
+        let mut nav = toy()?;
+        let none = std::iter::empty::<String>;
+
+        let samples = nav.samples(none(), 600, false, 42)?;
+        assert_eq!(samples.len(), 600);
+        let mut counts = HashMap::new();
+        for s in &samples {
+            *counts.entry(s.join(" ")).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert!(counts.values().all(|n| (150..250).contains(n)));
+
+        assert_eq!(nav.samples(none(), 5, false, 42)?, samples[..5].to_vec());
+        assert_ne!(nav.samples(none(), 20, false, 7)?, samples[..20].to_vec());
+        assert!(nav
+            .samples(["~c"].iter(), 10, false, 1)?
+            .iter()
+            .all(|s| !s.contains(&"c".to_owned())));
+        assert!(nav.samples(["a", "b"].iter(), 3, false, 1)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn estimate_frequencies() -> Result<()> {
+        let mut nav = toy()?;
+        let targets = ["e".to_owned(), "b".to_owned(), " a ".to_owned()];
+
+        let fs = nav.frequencies(std::iter::empty::<String>(), &targets, 600, false, 3)?;
+        assert_eq!(fs[0], ("e".to_owned(), 1.0));
+        assert!((fs[1].1 - 2.0 / 3.0).abs() < 0.1);
+        assert!((fs[2].1 - 1.0 / 3.0).abs() < 0.1);
+
+        let fs = nav.frequencies(["a", "b"].iter(), &targets, 10, false, 3)?;
+        assert!(fs.iter().all(|(_, f)| *f == 0.0));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn sample_via_clingo() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let samples = nav.samples(std::iter::empty::<String>(), 300, false, 42)?;
+        let a = samples
+            .iter()
+            .filter(|s| s.contains(&"a".to_owned()))
+            .count();
+        assert!((60..140).contains(&a));
+        assert!(samples.iter().all(|s| s.contains(&"e".to_owned())));
+
+        Ok(())
+    }
+}
@@ -1,346 +1,288 @@
-use super::super::lex;
-use super::Navigator;
+use super::backend::{Mode, SolverBackend};
+use super::{outf2, Navigator};
 use crate::nav::errors::NavigatorError;
-use crate::nav::utils::ToHashSet;
 use std::collections::{HashMap, HashSet};
-
+use std::fmt::Display;
+
+/// Collecting representative solutions of a (sub-)space of solutions.
+///
+/// All sieve variants search within the solutions under **route**, and, if **projecting** is
+/// set, consider solutions projected onto shown atoms only.
+///
+/// Each solve call assumes one of the remaining target atoms, so every collected solution
+/// contains at least one target atom without altering the program of the navigator. Target atoms
+/// must be known and positive; spelling does not matter, e.g., `p(1, 2)` denotes `p(1,2)`.
 pub trait Collect {
-    fn sieve(&mut self, target_atoms: &[String]) -> super::Result<()>;
-    fn sieve_quiet(&mut self, target_atoms: &[String]) -> Option<Vec<String>>;
-    fn sieve_verbose(&mut self, target_atoms: &[String]) -> super::Result<()>;
-    fn sieve_outf2(&mut self, target_atoms: &[String]) -> super::Result<Vec<String>>;
+    /// Prints solutions under **route** until every atom in **target_atoms** was observed.
+    fn sieve<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<()>;
+    /// Returns atoms in **target_atoms** that are true in some solution under **route**.
+    fn sieve_quiet<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> Option<Vec<String>>;
+    /// Like [sieve](Collect::sieve), but returns collected solutions instead of printing them,
+    /// and skips targets false in all solutions under **route** instead of stopping.
+    fn sieve_solutions<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<Vec<Vec<String>>>;
+    /// Like [sieve](Collect::sieve), but additionally prints frequencies of **target_atoms**.
+    ///
+    /// Frequencies are among collected solutions, found in solver order; see
+    /// [frequencies](super::sampling::Sample::frequencies) for frequencies over random samples,
+    /// which are uniform only while counts stay small, see [Sample](super::sampling::Sample).
+    fn sieve_verbose<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<()>;
+    /// Like [sieve](Collect::sieve), but returns solutions in format required by clingraph.
+    fn sieve_outf2<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<Vec<String>>;
 }
-impl Collect for Navigator {
-    fn sieve(&mut self, target_atoms: &[String]) -> super::Result<()> {
-        let mut or = ":-".to_owned();
-        target_atoms.iter().for_each(|atom| {
-            or = format!("{or} not {atom},");
-        });
-
-        or = format!("{}.", &or[..or.len() - 1]);
-        self.add_rule(or.clone())?;
+impl<B: SolverBackend> Collect for Navigator<B> {
+    fn sieve<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<()> {
+        let route = self.route(route);
 
         let mut i = 1;
-        let mut to_observe = target_atoms.to_vec().to_hashset();
-        let mut collection = vec![].to_hashset();
-
-        while !to_observe.is_empty() {
-            let target = to_observe
-                .iter()
-                .next()
-                .and_then(|a| self.expression_to_literal(a))
-                .ok_or(NavigatorError::None)?;
-
-            let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-            let mut solve_handle = ctl.solve(clingo::SolveMode::YIELD, &[target])?;
-
-            if solve_handle
-                .get()
-                .map(|r| r == clingo::SolveResult::SATISFIABLE)?
-                == false
-            {
+        let targets = targets(self, target_atoms)?;
+        let mut to_observe = targets.keys().cloned().collect::<HashSet<_>>();
+        let mut collection = HashSet::new();
+
+        while let Some(target) = to_observe.iter().next().cloned() {
+            let mut satisfiable = false;
+            self.backend.solve(
+                &[route.as_slice(), &[targets[&target]]].concat(),
+                Mode::Models,
+                projecting,
+                |atoms| {
+                    satisfiable = true;
+                    if observe(&mut to_observe, atoms, |_| ()) && collection.insert(atoms.to_vec())
+                    {
+                        println!("solution {:?}: ", i);
+                        atoms.iter().for_each(|atom| print!("{atom} "));
+                        i += 1;
+                        println!();
+
+                        return false;
+                    }
+                    true // did not observe anything new
+                },
+            )?;
+
+            if !satisfiable {
                 println!("info: cannot cover all target atoms");
                 println!("info: stopped search");
                 break;
             }
-
-            #[allow(clippy::needless_collect)]
-            while let Ok(Some(model)) = solve_handle.model() {
-                if let Ok(atoms) = model.symbols(clingo::ShowType::SHOWN) {
-                    match atoms
-                        .iter()
-                        .map(|a| to_observe.remove(&a.to_string()))
-                        .collect::<Vec<_>>()
-                        .iter()
-                        .any(|v| *v)
-                    {
-                        true => {
-                            if collection.insert(atoms.clone()) {
-                                println!("solution {:?}: ", i);
-                                let atoms_strings = atoms.iter().map(|atom| atom.to_string());
-                                atoms_strings.clone().for_each(|atom| print!("{} ", atom));
-                                i += 1;
-                                println!();
-
-                                break;
-                            }
-                        }
-                        _ => {
-                            solve_handle.resume()?;
-                            continue;
-                        } // did not observe anything new
-                    }
-                }
-            }
-
-            let ctl = solve_handle
-                .close()
-                .map_err(|e| NavigatorError::Clingo(e))?;
-            self.ctl = Some(ctl);
+            // target may be hidden while projecting, and so never observed
+            to_observe.remove(&target);
         }
 
-        self.remove_rule(or)
+        Ok(())
     }
 
-    fn sieve_quiet(&mut self, target_atoms: &[String]) -> Option<Vec<String>> {
-        //let mut or = ":-".to_owned();
-        //target_atoms.iter().for_each(|atom| {
-        //    or = format!("{or} not {atom},");
-        //});
+    fn sieve_quiet<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> Option<Vec<String>> {
+        let route = self.route(route);
 
-        //or = format!("{}.", &or[..or.len() - 1]);
-        //self.add_rule(or.clone()).ok()?;
-
-        let mut to_observe = target_atoms.to_vec().to_hashset();
+        let targets = targets(self, target_atoms).ok()?;
+        let mut to_observe = targets.keys().cloned().collect::<HashSet<_>>();
         let mut true_somewhere = vec![];
 
-        while !to_observe.is_empty() {
-            //dbg!(to_observe.len());
-            let (target_atom, target) = to_observe //.clone()
-                .iter()
-                .next()
-                .map(|a| (a.clone(), self.expression_to_literal(a).unwrap()))?;
-
-            let ctl = self.ctl.take()?;
-            let mut solve_handle = ctl.solve(clingo::SolveMode::YIELD, &[target]).ok()?;
-
-            if solve_handle
-                .get()
-                .map(|r| r == clingo::SolveResult::SATISFIABLE)
-                .ok()?
-                == false
-            {
-                to_observe.remove(&target_atom);
-
-                let ctl = solve_handle.close().ok()?;
-                self.ctl = Some(ctl);
-                continue;
+        while let Some(target) = to_observe.iter().next().cloned() {
+            let mut satisfiable = false;
+            self.backend
+                .solve(
+                    &[route.as_slice(), &[targets[&target]]].concat(),
+                    Mode::Models,
+                    projecting,
+                    |atoms| {
+                        satisfiable = true;
+                        !observe(&mut to_observe, atoms, |a| true_somewhere.push(a))
+                    },
+                )
+                .ok()?;
+
+            // target may be hidden while projecting, and so never observed
+            if to_observe.remove(&target) && satisfiable {
+                true_somewhere.push(target);
             }
+        }
 
-            #[allow(clippy::needless_collect)]
-            while let Ok(Some(model)) = solve_handle.model() {
-                if let Ok(atoms) = model.symbols(clingo::ShowType::SHOWN) {
-                    match atoms
-                        .iter()
-                        .map(|a| {
-                            let v = to_observe.remove(&a.to_string());
-                            if v {
-                                true_somewhere.push(a.to_string())
-                            }
-                            v
-                        })
-                        .collect::<Vec<_>>()
-                        .iter()
-                        .any(|v| *v)
-                    {
-                        true => {
-                            break;
-                        }
-                        _ => {
-                            solve_handle.resume().ok()?;
-                            continue;
-                        } // did not observe anything new
+        Some(true_somewhere)
+    }
+
+    fn sieve_solutions<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<Vec<Vec<String>>> {
+        let route = self.route(route);
+
+        let targets = targets(self, target_atoms)?;
+        let mut to_observe = targets.keys().cloned().collect::<HashSet<_>>();
+        let mut collection = vec![];
+
+        while let Some(target) = to_observe.iter().next().cloned() {
+            self.backend.solve(
+                &[route.as_slice(), &[targets[&target]]].concat(),
+                Mode::Models,
+                projecting,
+                |atoms| {
+                    let solution = atoms.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+                    if observe(&mut to_observe, atoms, |_| ()) && !collection.contains(&solution) {
+                        collection.push(solution);
+
+                        return false;
                     }
-                }
-            }
+                    true // did not observe anything new
+                },
+            )?;
 
-            let ctl = solve_handle.close().ok()?;
-            self.ctl = Some(ctl);
+            // target may be hidden while projecting, or false in all solutions
+            to_observe.remove(&target);
         }
 
-        //self.remove_rule(or).ok()?;
-
-        Some(true_somewhere)
+        Ok(collection)
     }
 
-    fn sieve_outf2(&mut self, target_atoms: &[String]) -> super::Result<Vec<String>> {
+    fn sieve_outf2<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<Vec<String>> {
+        let route = self.route(route);
+
         let mut out = vec![];
 
-        let mut or = ":-".to_owned();
-        target_atoms.iter().for_each(|atom| {
-            or = format!("{or} not {atom},");
-        });
+        let mut i = 1;
+        let targets = targets(self, target_atoms)?;
+        let mut to_observe = targets.keys().cloned().collect::<HashSet<_>>();
+        let mut collection = HashSet::new();
+
+        while let Some(target) = to_observe.iter().next().cloned() {
+            let mut satisfiable = false;
+            self.backend.solve(
+                &[route.as_slice(), &[targets[&target]]].concat(),
+                Mode::Models,
+                projecting,
+                |atoms| {
+                    satisfiable = true;
+                    if observe(&mut to_observe, atoms, |_| ()) && collection.insert(atoms.to_vec())
+                    {
+                        println!("solution {:?}: ", i);
+                        out.push(outf2(atoms));
+                        i += 1;
+                        println!();
 
-        or = format!("{}.", &or[..or.len() - 1]);
-        self.add_rule(or.clone())?;
+                        return false;
+                    }
+                    true // did not observe anything new
+                },
+            )?;
 
-        let mut i = 1;
-        let mut to_observe = target_atoms.to_vec().to_hashset();
-        let mut collection = vec![].to_hashset();
-
-        while !to_observe.is_empty() {
-            let target = to_observe
-                .iter()
-                .next()
-                .and_then(|a| self.expression_to_literal(a))
-                .ok_or(NavigatorError::None)?;
-
-            let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-            let mut solve_handle = ctl.solve(clingo::SolveMode::YIELD, &[target])?;
-
-            if solve_handle
-                .get()
-                .map(|r| r == clingo::SolveResult::SATISFIABLE)?
-                == false
-            {
+            if !satisfiable {
                 println!("info: cannot cover all target atoms");
                 println!("info: stopped search");
                 break;
             }
-
-            #[allow(clippy::needless_collect)]
-            while let Ok(Some(model)) = solve_handle.model() {
-                if let Ok(atoms) = model.symbols(clingo::ShowType::SHOWN) {
-                    match atoms
-                        .iter()
-                        .map(|a| to_observe.remove(&a.to_string()))
-                        .collect::<Vec<_>>()
-                        .iter()
-                        .any(|v| *v)
-                    {
-                        true => {
-                            if collection.insert(atoms.clone()) {
-                                println!("solution {:?}: ", i);
-                                let mut answer_set =
-                                    "{\"Solver\": \"\", \"Input\": [\"\"], ".to_owned();
-                                answer_set = format!(
-                                    "{answer_set}\"Call\": [ {{ \"Witnesses\": [ {{ \"Value\": ["
-                                );
-                                if let Some((last, rest)) = atoms.split_last() {
-                                    for atom in rest {
-                                        answer_set =
-                                            format!("{answer_set}{:?}, ", atom.to_string());
-                                    }
-                                    answer_set = format!(
-                            "{answer_set}{:?}]}}]}}],\n\"Result\":\"SATISFIABLE\",{}{}{}",
-                            last.to_string(),
-                            "\n\"Models\":{\"Number\":1,\"More\":\"yes\"},\n\"Calls\": 1,\n",
-                            "\"Time\":{\"Total\": 0.000,\"Solve\": 0.000,",
-                            "\"Model\": 0.000,\"Unsat\": 0.000,\"CPU\": 0.000}}\n"
-                        );
-                                }
-
-                                i += 1;
-                                out.push(answer_set);
-                                println!();
-
-                                break;
-                            }
-                        }
-                        _ => {
-                            solve_handle.resume()?;
-                            continue;
-                        } // did not observe anything new
-                    }
-                }
-            }
-
-            let ctl = solve_handle
-                .close()
-                .map_err(|e| NavigatorError::Clingo(e))?;
-            self.ctl = Some(ctl);
+            // target may be hidden while projecting, and so never observed
+            to_observe.remove(&target);
         }
 
-        self.remove_rule(or)?;
-
         Ok(out)
     }
 
-    fn sieve_verbose(&mut self, target_atoms: &[String]) -> super::Result<()> {
-        let mut or = ":-".to_owned();
-        target_atoms.iter().for_each(|atom| {
-            or = format!("{or} not {atom},");
-        });
-
-        or = format!("{}.", &or[..or.len() - 1]);
-        self.add_rule(or.clone())?;
+    fn sieve_verbose<S: ToString>(
+        &mut self,
+        route: impl Iterator<Item = S>,
+        target_atoms: &[String],
+        projecting: bool,
+    ) -> super::Result<()> {
+        let route = self.route(route);
 
         let mut n = 0;
-        let mut freq_table: HashMap<clingo::Symbol, usize> = HashMap::new();
-        target_atoms.iter().for_each(|atom| {
+        let mut freq_table: HashMap<B::Atom, usize> = HashMap::new();
+        for atom in target_atoms {
             n += 1;
-            freq_table.insert(unsafe { lex::parse(&atom).unwrap_unchecked() }, 0);
-        });
-        let mut chunks_table: HashMap<usize, HashSet<clingo::Symbol>> = HashMap::new();
+            let atom = self
+                .backend
+                .parse_atom(atom)
+                .ok_or_else(|| NavigatorError::InvalidInput(atom.clone()))?;
+            freq_table.insert(atom, 0);
+        }
+        let mut chunks_table: HashMap<usize, HashSet<B::Atom>> = HashMap::new();
         let mut population_size = 0;
         let mut i = 1;
-        let mut to_observe = target_atoms.to_vec().to_hashset();
-        let mut collection = vec![].to_hashset();
-
-        while !to_observe.is_empty() {
-            let target = to_observe
-                .iter()
-                .next()
-                .and_then(|a| self.expression_to_literal(a))
-                .ok_or(NavigatorError::None)?;
-
-            let ctl = self.ctl.take().ok_or(NavigatorError::NoControl)?;
-            let mut solve_handle = ctl.solve(clingo::SolveMode::YIELD, &[target])?;
-
-            if solve_handle
-                .get()
-                .map(|r| r == clingo::SolveResult::SATISFIABLE)?
-                == false
-            {
-                println!("info: cannot cover all target atoms");
-                println!("info: stopped search");
-                break;
-            }
-
-            #[allow(clippy::needless_collect)]
-            while let Ok(Some(model)) = solve_handle.model() {
-                if let Ok(atoms) = model.symbols(clingo::ShowType::SHOWN) {
-                    match atoms
-                        .iter()
-                        .map(|a| to_observe.remove(&a.to_string()))
-                        .collect::<Vec<_>>()
-                        .iter()
-                        .any(|v| *v)
+        let targets = targets(self, target_atoms)?;
+        let mut to_observe = targets.keys().cloned().collect::<HashSet<_>>();
+        let mut collection = HashSet::new();
+
+        while let Some(target) = to_observe.iter().next().cloned() {
+            let mut satisfiable = false;
+            self.backend.solve(
+                &[route.as_slice(), &[targets[&target]]].concat(),
+                Mode::Models,
+                projecting,
+                |atoms| {
+                    satisfiable = true;
+                    if observe(&mut to_observe, atoms, |_| ()) && collection.insert(atoms.to_vec())
                     {
-                        true => {
-                            if collection.insert(atoms.clone()) {
-                                atoms.iter().for_each(|atom| {
-                                    if let Some(count) = freq_table.get_mut(atom) {
-                                        *count += 1;
-                                    }
-                                });
-
-                                println!("solution {:?}: ", i);
-                                let atoms_strings = atoms.iter().map(|atom| atom.to_string());
-                                atoms_strings.clone().for_each(|atom| print!("{} ", atom));
-                                i += 1;
-                                println!();
-
-                                break;
+                        atoms.iter().for_each(|atom| {
+                            if let Some(count) = freq_table.get_mut(atom) {
+                                *count += 1;
                             }
-                        }
-                        _ => {
-                            solve_handle.resume()?;
-                            continue;
-                        } // did not observe anything new
+                        });
+
+                        println!("solution {:?}: ", i);
+                        atoms.iter().for_each(|atom| print!("{atom} "));
+                        i += 1;
+                        println!();
+
+                        return false;
                     }
-                }
-            }
+                    true // did not observe anything new
+                },
+            )?;
 
-            let ctl = solve_handle
-                .close()
-                .map_err(|e| NavigatorError::Clingo(e))?;
-            self.ctl = Some(ctl);
+            if !satisfiable {
+                println!("info: cannot cover all target atoms");
+                println!("info: stopped search");
+                break;
+            }
+            // target may be hidden while projecting, and so never observed
+            to_observe.remove(&target);
         }
 
         freq_table.iter().for_each(|(atom, freq)| {
             population_size += *freq;
-            //let freq_chunk = chunks_table
-            //    .raw_entry_mut()
-            //    .from_key(freq)
-            //    .or_insert_with(|| (*freq, vec![*atom].to_hashset()));
-            //freq_chunk.1.insert(*atom);
-            chunks_table
-                .entry(*freq)
-                .and_modify(|s| {
-                    s.insert(*atom);
-                })
-                .or_insert_with(|| vec![*atom].to_hashset());
+            chunks_table.entry(*freq).or_default().insert(atom.clone());
         });
         let div = 2f64.powf(entropy(&freq_table, population_size as f64));
         let r = {
@@ -352,21 +294,62 @@ impl Collect for Navigator {
         let c = (i - 1) as f64;
         freq_table
             .iter()
-            .for_each(|(k, v)| println!("{:.2} {}", *v as f64 / c, k.to_string()));
+            .for_each(|(k, v)| println!("{:.2} {}", *v as f64 / c, k));
         println!(
             "{:?} {:?}",
             freq_table.values().filter(|v| **v != 0).count() as f64 / n as f64,
             r
         );
         println!("-");
-        self.remove_rule(or)
+        Ok(())
     }
 }
 
-fn entropy(lookup_table: &HashMap<clingo::Symbol, usize>, sample_size: f64) -> f64 {
+/// Returns literals of **target_atoms** by their canonical spelling, or an error, if any is
+/// negated or unknown, as it could never be observed.
+fn targets<B: SolverBackend>(
+    nav: &Navigator<B>,
+    target_atoms: &[String],
+) -> super::Result<HashMap<String, B::Literal>> {
+    let mut targets = HashMap::new();
+    for atom in target_atoms {
+        let literal = match atom.trim().starts_with('~') {
+            true => None,
+            _ => nav
+                .backend
+                .parse_atom(atom)
+                .and_then(|a| nav.literals.get(&a).map(|l| (a.to_string(), *l))),
+        };
+        let (atom, literal) = literal.ok_or_else(|| NavigatorError::InvalidInput(atom.clone()))?;
+        targets.insert(atom, literal);
+    }
+
+    Ok(targets)
+}
+
+/// Removes atoms of **model** from **to_observe**, passes each removed atom to **observed**, and
+/// returns whether any atom was removed.
+fn observe(
+    to_observe: &mut HashSet<String>,
+    model: &[impl Display],
+    mut observed: impl FnMut(String),
+) -> bool {
+    let mut any = false;
+    for atom in model {
+        let atom = atom.to_string();
+        if to_observe.remove(&atom) {
+            observed(atom);
+            any = true;
+        }
+    }
+
+    any
+}
+
+fn entropy<A>(lookup_table: &HashMap<A, usize>, sample_size: f64) -> f64 {
     -lookup_table
-        .iter()
-        .map(|(_, count)| *count as f64 / sample_size)
+        .values()
+        .map(|count| *count as f64 / sample_size)
         .map(|probability| probability * probability.log2())
         .sum::<f64>()
 }
@@ -375,11 +358,116 @@ fn entropy(lookup_table: &HashMap<clingo::Symbol, usize>, sample_size: f64) -> f
 mod tests {
     use super::super::errors::Result;
     use super::*;
+    use crate::nav::backend::Memory;
+
+    #[test]
+    fn sieve_any_spelling_of_positive_targets() -> Result<()> {
+        let answer_sets = vec![vec!["a", "p(1,2)"], vec!["b", "c"], vec!["b", "d"]];
+        let mut nav = Navigator::with_backend(Memory::new(answer_sets), "")?;
+        let none = std::iter::empty::<String>;
+        let targets = ["p(1, 2)".to_owned(), " c".to_owned()];
+
+        let mut xs = nav
+            .sieve_quiet(none(), &targets, false)
+            .ok_or(NavigatorError::None)?;
+        xs.sort();
+        assert_eq!(xs, vec!["c".to_owned(), "p(1,2)".to_owned()]);
+        nav.sieve(none(), &targets, false)?;
+        nav.sieve_verbose(none(), &targets, false)?;
+        assert_eq!(nav.sieve_outf2(none(), &targets, false)?.len(), 2);
+        let mut xs = nav.sieve_solutions(none(), &targets, false)?;
+        xs.sort();
+        assert_eq!(
+            xs,
+            vec![
+                vec!["a".to_owned(), "p(1,2)".to_owned()],
+                vec!["b".to_owned(), "c".to_owned()]
+            ]
+        );
+        let xs = nav.sieve_solutions(["~b"].iter(), &targets, false)?;
+        assert_eq!(xs, vec![vec!["a".to_owned(), "p(1,2)".to_owned()]]);
+
+        for target in ["~a", "f"] {
+            let targets = [target.to_owned()];
+            assert!(nav.sieve_quiet(none(), &targets, false).is_none());
+            assert!(matches!(
+                nav.sieve(none(), &targets, false),
+                Err(NavigatorError::InvalidInput(_))
+            ));
+            assert!(nav.sieve_verbose(none(), &targets, false).is_err());
+            assert!(nav.sieve_outf2(none(), &targets, false).is_err());
+            assert!(nav.sieve_solutions(none(), &targets, false).is_err());
+        }
 
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn sieve_hidden_targets() -> Result<()> {
+        let mut nav = Navigator::new(
+            "a;b. c;d :- b. e. #show a/0. #show b/0.",
+            vec!["0".to_string()],
+        )?;
+        let targets = ["c".to_owned(), "d".to_owned()];
+
+        let xs = nav
+            .sieve_quiet(std::iter::empty::<String>(), &targets, true)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(xs.len(), 2);
+        nav.sieve(std::iter::empty::<String>(), &targets, true)?;
+        nav.sieve_outf2(std::iter::empty::<String>(), &targets, true)?;
+        let xs = nav.sieve_solutions(std::iter::empty::<String>(), &targets, true)?;
+        assert!(xs.is_empty());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
     #[test]
     fn soe_sieve() -> Result<()> {
         let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
-        nav.sieve(&["a".to_owned(), "b".to_owned()])?;
-        nav.sieve_verbose(&["a".to_owned(), "b".to_owned()])
+        nav.sieve(
+            std::iter::empty::<String>(),
+            &["a".to_owned(), "b".to_owned()],
+            false,
+        )?;
+        nav.sieve_verbose(
+            std::iter::empty::<String>(),
+            &["a".to_owned(), "b".to_owned()],
+            false,
+        )?;
+        assert_eq!(nav.program(), "a;b. c;d :- b. e.");
+
+        nav.sieve_outf2(["b"].iter(), &["c".to_owned(), "d".to_owned()], false)?;
+        assert_eq!(nav.program(), "a;b. c;d :- b. e.");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "clingo")]
+    #[test]
+    fn soe_sieve_quiet_under_route() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+        let targets = ["a".to_owned(), "c".to_owned(), "d".to_owned()];
+
+        let xs = nav
+            .sieve_quiet(std::iter::empty::<String>(), &targets, false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(xs.len(), 3);
+
+        let xs = nav
+            .sieve_quiet(["b"].iter(), &targets, false)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(xs.len(), 2);
+        assert!(!xs.contains(&"a".to_owned()));
+
+        let xs = nav
+            .sieve_quiet(["~c"].iter(), &targets, true)
+            .ok_or(NavigatorError::None)?;
+        assert_eq!(xs.len(), 2);
+        assert!(!xs.contains(&"c".to_owned()));
+
+        Ok(())
     }
 }
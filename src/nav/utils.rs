@@ -2,7 +2,6 @@ use std::collections::HashSet;
 
 pub(crate) trait ToHashSet<T> {
     fn to_hashset(&self) -> HashSet<T>;
-    fn difference(&self, other: &[T]) -> Vec<T>;
     fn difference_as_set(&self, other: &[T]) -> HashSet<T>;
 }
 impl<T> ToHashSet<T> for Vec<T>
@@ -12,12 +11,6 @@ where
     fn to_hashset(&self) -> HashSet<T> {
         self.iter().cloned().collect::<HashSet<_>>()
     }
-    fn difference(&self, other: &[T]) -> Vec<T> {
-        let x = self.to_hashset();
-        let y = &other.to_vec().to_hashset();
-
-        x.difference(y).cloned().collect::<Vec<_>>()
-    }
     fn difference_as_set(&self, other: &[T]) -> HashSet<T> {
         let x = self.to_hashset();
         let y = &other.to_vec().to_hashset();
@@ -25,3 +18,33 @@ where
         x.difference(y).cloned().collect::<HashSet<_>>()
     }
 }
+
+/// Pseudorandom number generator (SplitMix64), so results are reproducible from a seed across
+/// platforms and releases.
+pub(crate) struct Rng(u64);
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+        z ^ (z >> 31)
+    }
+
+    /// Returns number in `0..n` for positive **n**, without modulo bias.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        let n = n as u64;
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return (x % n) as usize;
+            }
+        }
+    }
+}
@@ -1,24 +1,90 @@
-use super::facets::{consequences_count, consequences_count_projecting};
+use super::backend::{Mode, SolverBackend};
+use super::facets::{consequences_count, consequences_count_projecting, Facets};
 use super::Navigator;
 
 /// Returns count of specified weighting function under route.
-pub fn count<S: ToString>(
+pub fn count<B: SolverBackend, S: ToString>(
     weighting_function: &mut impl WeightingFunction,
-    nav: &mut Navigator,
+    nav: &mut Navigator<B>,
     route: impl Iterator<Item = S>,
 ) -> Option<usize> {
     weighting_function.count(nav, route)
 }
 
 /// Returns count of specified weighting function under route, while projecting on shown atoms.
-pub fn count_projecting<S: ToString>(
+pub fn count_projecting<B: SolverBackend, S: ToString>(
     weighting_function: &mut impl WeightingFunction,
-    nav: &mut Navigator,
+    nav: &mut Navigator<B>,
     route: impl Iterator<Item = S>,
 ) -> Option<usize> {
     weighting_function.count_projecting(nav, route)
 }
 
+/// Returns weights of facets under **route**, i.e., by how much activating each facet reduces
+/// the count of specified weighting function under **route**.
+///
+/// Facets are formed by facet-inducing atoms under **route**, each atom `a` followed by `~a`.
+pub fn facet_weights<B: SolverBackend>(
+    weighting_function: &mut impl WeightingFunction,
+    nav: &mut Navigator<B>,
+    route: &[String],
+) -> Option<Vec<(String, usize)>> {
+    let total = weighting_function.count(nav, route.iter())?;
+    let atoms = nav.facet_inducing_atoms(route.iter())?;
+
+    weigh(
+        atoms.into_iter().map(|a| a.to_string()).collect(),
+        route,
+        |r| {
+            weighting_function
+                .count(nav, r.iter())
+                .map(|c| total.saturating_sub(c))
+        },
+    )
+}
+
+/// Returns weights of facets under **route**, i.e., by how much activating each facet reduces
+/// the count of specified weighting function under **route**, while projecting on shown atoms.
+///
+/// Facets are formed by facet-inducing atoms under **route**, each atom `a` followed by `~a`.
+pub fn facet_weights_projecting<B: SolverBackend>(
+    weighting_function: &mut impl WeightingFunction,
+    nav: &mut Navigator<B>,
+    route: &[String],
+) -> Option<Vec<(String, usize)>> {
+    let total = weighting_function.count_projecting(nav, route.iter())?;
+    let atoms = nav.facet_inducing_atoms_projecting(route.iter())?;
+
+    weigh(
+        atoms.into_iter().map(|a| a.to_string()).collect(),
+        route,
+        |r| {
+            weighting_function
+                .count_projecting(nav, r.iter())
+                .map(|c| total.saturating_sub(c))
+        },
+    )
+}
+
+fn weigh(
+    mut atoms: Vec<String>,
+    route: &[String],
+    mut weight_under: impl FnMut(&[String]) -> Option<usize>,
+) -> Option<Vec<(String, usize)>> {
+    atoms.sort();
+
+    let mut ws = vec![];
+    for atom in atoms {
+        for facet in [atom.clone(), format!("~{atom}")] {
+            let mut extended = route.to_vec();
+            extended.push(facet.clone());
+            ws.push((facet, weight_under(&extended)?));
+        }
+    }
+
+    Some(ws)
+}
+
 /// The weight of a facet.
 #[derive(Debug, Clone)]
 pub enum Weight {
@@ -28,60 +94,55 @@ pub enum Weight {
 
 /// Implements counting procedures.
 pub trait WeightingFunction {
-    fn count<S: ToString>(
+    fn count<B: SolverBackend, S: ToString>(
         &mut self,
-        nav: &mut Navigator,
+        nav: &mut Navigator<B>,
         route: impl Iterator<Item = S>,
     ) -> Option<usize>;
-    fn count_projecting<S: ToString>(
+    fn count_projecting<B: SolverBackend, S: ToString>(
         &mut self,
-        nav: &mut Navigator,
+        nav: &mut Navigator<B>,
         route: impl Iterator<Item = S>,
     ) -> Option<usize>;
 }
 impl WeightingFunction for Weight {
-    fn count<S: ToString>(
+    fn count<B: SolverBackend, S: ToString>(
         &mut self,
-        nav: &mut Navigator,
+        nav: &mut Navigator<B>,
         peek_on: impl Iterator<Item = S>,
     ) -> Option<usize> {
         match self {
             Self::FacetCounting => {
-                let route = peek_on
-                    .map(|s| nav.expression_to_literal(s))
-                    .flatten()
-                    .collect::<Vec<_>>();
+                let route = nav.route(peek_on);
 
-                let brave_consequences_count = consequences_count(nav, &route, "brave");
+                let brave_consequences_count = consequences_count(nav, &route, Mode::Brave);
                 if brave_consequences_count == Some(0) {
                     brave_consequences_count
                 } else {
                     brave_consequences_count.and_then(|bcs| {
-                        consequences_count(nav, &route, "cautious").map(|ccs| 2 * (bcs - ccs))
+                        consequences_count(nav, &route, Mode::Cautious).map(|ccs| 2 * (bcs - ccs))
                     })
                 }
             }
             Self::AnswerSetCounting => nav.enumerate_solutions_quietly(None, peek_on).ok(),
         }
     }
-    fn count_projecting<S: ToString>(
+    fn count_projecting<B: SolverBackend, S: ToString>(
         &mut self,
-        nav: &mut Navigator,
+        nav: &mut Navigator<B>,
         peek_on: impl Iterator<Item = S>,
     ) -> Option<usize> {
         match self {
             Self::FacetCounting => {
-                let route = peek_on
-                    .map(|s| nav.expression_to_literal(s))
-                    .flatten()
-                    .collect::<Vec<_>>();
+                let route = nav.route(peek_on);
 
-                let brave_consequences_count = consequences_count_projecting(nav, &route, "brave");
+                let brave_consequences_count =
+                    consequences_count_projecting(nav, &route, Mode::Brave);
                 if brave_consequences_count == Some(0) {
                     brave_consequences_count
                 } else {
                     brave_consequences_count.and_then(|bcs| {
-                        consequences_count_projecting(nav, &route, "cautious")
+                        consequences_count_projecting(nav, &route, Mode::Cautious)
                             .map(|ccs| 2 * (bcs - ccs))
                     })
                 }
@@ -91,7 +152,7 @@ impl WeightingFunction for Weight {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "clingo"))]
 mod tests {
     use super::super::errors::Result;
     use super::*;
@@ -208,6 +269,40 @@ mod tests {
         let c = count(&mut w, &mut nav, ["a"].iter()).ok_or(NavigatorError::None)?;
         assert_eq!(c, 2);
 
+        Ok(())
+    }
+    #[test]
+    fn weights_of_facets() -> Result<()> {
+        let mut nav = Navigator::new("a;b. c;d :- b. e.", vec!["0".to_string()])?;
+
+        let mut w = Weight::FacetCounting;
+        let ws = facet_weights(&mut w, &mut nav, &[]).ok_or(NavigatorError::None)?;
+        assert_eq!(
+            ws,
+            vec![
+                ("a".to_owned(), 8),
+                ("~a".to_owned(), 4),
+                ("b".to_owned(), 4),
+                ("~b".to_owned(), 8),
+                ("c".to_owned(), 8),
+                ("~c".to_owned(), 2),
+                ("d".to_owned(), 8),
+                ("~d".to_owned(), 2),
+            ]
+        );
+
+        let mut w = Weight::AnswerSetCounting;
+        let ws = facet_weights(&mut w, &mut nav, &["b".to_owned()]).ok_or(NavigatorError::None)?;
+        assert_eq!(
+            ws,
+            vec![
+                ("c".to_owned(), 1),
+                ("~c".to_owned(), 1),
+                ("d".to_owned(), 1),
+                ("~d".to_owned(), 1),
+            ]
+        );
+
         Ok(())
     }
 }
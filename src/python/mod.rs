@@ -0,0 +1,209 @@
+use crate::lex;
+use crate::nav::errors;
+use crate::nav::facets::{Facets, TruthValue};
+use crate::nav::soe::Collect;
+use crate::nav::weights::{self, facet_weights, facet_weights_projecting};
+use crate::nav::Navigator;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::BTreeSet;
+
+create_exception!(savan, NavigatorError, PyException);
+
+fn error(e: errors::NavigatorError) -> PyErr {
+    NavigatorError::new_err(e.to_string())
+}
+
+/// Returns the result of a navigator call reporting failed solving by `None`.
+fn solved<T>(x: Option<T>) -> PyResult<T> {
+    x.ok_or_else(|| error(errors::NavigatorError::None))
+}
+
+fn sorted(atoms: impl IntoIterator<Item = clingo::Symbol>) -> Vec<String> {
+    let mut atoms = atoms.into_iter().map(lex::repr).collect::<Vec<_>>();
+    atoms.sort();
+
+    atoms
+}
+
+/// Weighting function, see [Weight](crate::nav::weights::Weight).
+#[pyclass(name = "Weight", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PyWeight {
+    AnswerSetCounting,
+    FacetCounting,
+}
+impl From<PyWeight> for weights::Weight {
+    fn from(w: PyWeight) -> Self {
+        match w {
+            PyWeight::AnswerSetCounting => Self::AnswerSetCounting,
+            PyWeight::FacetCounting => Self::FacetCounting,
+        }
+    }
+}
+
+/// Python view of a [Navigator](crate::nav::Navigator).
+///
+/// Routes are lists of facets such as `["a", "~b(1)"]`; methods raise `savan.NavigatorError`,
+/// if solving fails.
+#[pyclass(name = "Navigator", unsendable)]
+pub struct PyNavigator {
+    nav: Navigator,
+}
+
+#[pymethods]
+impl PyNavigator {
+    #[new]
+    #[pyo3(signature = (program, args = vec!["0".to_owned()]))]
+    fn new(program: String, args: Vec<String>) -> PyResult<Self> {
+        Ok(Self {
+            nav: Navigator::new(program, args).map_err(error)?,
+        })
+    }
+
+    /// Returns atoms of the ground program in sorted order.
+    fn atoms(&self) -> Vec<String> {
+        let mut atoms = self.nav.atoms().collect::<Vec<_>>();
+        atoms.sort();
+
+        atoms
+    }
+
+    /// Checks whether **atom** is part of the ground program.
+    fn is_known(&self, atom: String) -> Option<bool> {
+        self.nav.is_known(atom)
+    }
+
+    /// Returns up to **limit** solutions under **route**, each as sorted list of atoms.
+    #[pyo3(signature = (route = vec![], limit = None))]
+    fn models(&mut self, route: Vec<String>, limit: Option<usize>) -> PyResult<Vec<Vec<String>>> {
+        Ok(self
+            .nav
+            .solutions(limit, route.iter())
+            .map_err(error)?
+            .into_iter()
+            .map(sorted)
+            .collect())
+    }
+
+    /// Returns brave consequences under **route**.
+    #[pyo3(signature = (route = vec![], project = false))]
+    fn brave_consequences(
+        &mut self,
+        route: Vec<String>,
+        project: bool,
+    ) -> PyResult<BTreeSet<String>> {
+        let xs = match project {
+            true => self.nav.brave_consequences_projecting(route.iter()),
+            _ => self.nav.brave_consequences(route.iter()),
+        };
+
+        Ok(solved(xs)?.into_iter().map(lex::repr).collect())
+    }
+
+    /// Returns cautious consequences under **route**.
+    #[pyo3(signature = (route = vec![], project = false))]
+    fn cautious_consequences(
+        &mut self,
+        route: Vec<String>,
+        project: bool,
+    ) -> PyResult<BTreeSet<String>> {
+        let xs = match project {
+            true => self.nav.cautious_consequences_projecting(route.iter()),
+            _ => self.nav.cautious_consequences(route.iter()),
+        };
+
+        Ok(solved(xs)?.into_iter().map(lex::repr).collect())
+    }
+
+    /// Returns facet-inducing atoms under **route**.
+    #[pyo3(signature = (route = vec![], project = false))]
+    fn facet_inducing_atoms(
+        &mut self,
+        route: Vec<String>,
+        project: bool,
+    ) -> PyResult<BTreeSet<String>> {
+        let xs = match project {
+            true => self.nav.facet_inducing_atoms_projecting(route.iter()),
+            _ => self.nav.facet_inducing_atoms(route.iter()),
+        };
+
+        Ok(solved(xs)?.into_iter().map(lex::repr).collect())
+    }
+
+    /// Returns truth values `"T"`, `"U"` or `"F"` of **atoms** under **route**.
+    #[pyo3(signature = (atoms, route = vec![], project = false))]
+    fn truth_values<'py>(
+        &mut self,
+        py: Python<'py>,
+        atoms: Vec<String>,
+        route: Vec<String>,
+        project: bool,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let table = match project {
+            true => self.nav.truth_values_projecting(route.iter(), &atoms),
+            _ => self.nav.truth_values(route.iter(), &atoms),
+        };
+
+        let dict = PyDict::new(py);
+        for (atom, v) in solved(table)? {
+            let v = match v {
+                TruthValue::True => "T",
+                TruthValue::Undecided => "U",
+                TruthValue::False => "F",
+            };
+            dict.set_item(atom, v)?;
+        }
+
+        Ok(dict)
+    }
+
+    /// Returns count of **weight** under **route**.
+    #[pyo3(signature = (route = vec![], weight = PyWeight::AnswerSetCounting, project = false))]
+    fn count(&mut self, route: Vec<String>, weight: PyWeight, project: bool) -> PyResult<usize> {
+        let mut w = weights::Weight::from(weight);
+        solved(match project {
+            true => weights::count_projecting(&mut w, &mut self.nav, route.iter()),
+            _ => weights::count(&mut w, &mut self.nav, route.iter()),
+        })
+    }
+
+    /// Returns weights of facets under **route** w.r.t. **weight**, ordered as
+    /// [facet_weights](crate::nav::weights::facet_weights).
+    #[pyo3(signature = (route = vec![], weight = PyWeight::FacetCounting, project = false))]
+    fn weights(
+        &mut self,
+        route: Vec<String>,
+        weight: PyWeight,
+        project: bool,
+    ) -> PyResult<Vec<(String, usize)>> {
+        let mut w = weights::Weight::from(weight);
+        solved(match project {
+            true => facet_weights_projecting(&mut w, &mut self.nav, &route),
+            _ => facet_weights(&mut w, &mut self.nav, &route),
+        })
+    }
+
+    /// Returns atoms of **targets** that are true in some solution under **route**.
+    #[pyo3(signature = (targets, route = vec![], project = false))]
+    fn sieve(
+        &mut self,
+        targets: Vec<String>,
+        route: Vec<String>,
+        project: bool,
+    ) -> PyResult<Vec<String>> {
+        solved(self.nav.sieve_quiet(route.iter(), &targets, project))
+    }
+}
+
+/// Python module `savan`.
+#[pymodule]
+fn savan(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyNavigator>()?;
+    m.add_class::<PyWeight>()?;
+    m.add("NavigatorError", m.py().get_type::<NavigatorError>())?;
+
+    Ok(())
+}
@@ -0,0 +1,509 @@
+use crate::lex;
+use crate::nav::errors::{NavigatorError, Result};
+use crate::nav::facets::Facets;
+use crate::nav::soe::Collect;
+use crate::nav::weights::{facet_weights, facet_weights_projecting, Weight};
+use crate::nav::Navigator;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const NAVIGATOR_ERROR: i64 = -32000;
+const UNKNOWN_SESSION: i64 = -32001;
+
+/// Maximal number of models returned per `models` request.
+const MAX_LIMIT: usize = 1000;
+/// Maximal number of models skipped per `models` request.
+const MAX_OFFSET: usize = 100 * MAX_LIMIT;
+
+struct Session {
+    nav: Navigator,
+    route: Vec<String>,
+    last_used: Instant,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+impl From<NavigatorError> for RpcError {
+    fn from(e: NavigatorError) -> Self {
+        Self {
+            code: NAVIGATOR_ERROR,
+            message: e.to_string(),
+        }
+    }
+}
+impl From<lex::ParseError> for RpcError {
+    fn from(e: lex::ParseError) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: e.to_string(),
+        }
+    }
+}
+
+fn invalid_params(message: impl Into<String>) -> RpcError {
+    RpcError {
+        code: INVALID_PARAMS,
+        message: message.into(),
+    }
+}
+
+/// JSON-RPC 2.0 server exposing navigation sessions via HTTP `POST` requests.
+///
+/// Requests are handled one after another on the thread calling [run](Server::run), which owns
+/// all sessions. Sessions idle for longer than the configured time to live are closed.
+///
+/// | method | params | result |
+/// |---|---|---|
+/// | `create_session` | `program`, `args` (default `["0"]`) | `session` |
+/// | `close_session` | `session` | `true` |
+/// | `route` | `session` | `route` |
+/// | `activate` | `session`, `facets` | `route` |
+/// | `deactivate` | `session`, `facets` | `route` |
+/// | `facets` | `session`, `weight` (`"facets"` or `"answer-sets"`), `project` | `route`, `facets` |
+/// | `models` | `session`, `offset` (default 0, at most 100000), `limit` (default 10, at most 1000) | `route`, `offset`, `models` |
+/// | `sieve` | `session`, `targets`, `project` | `covered`, `uncovered` |
+pub struct Server {
+    http: tiny_http::Server,
+    sessions: HashMap<u64, Session>,
+    next_id: u64,
+    ttl: Duration,
+}
+impl Server {
+    /// Binds server to **addr**; sessions idle for longer than **ttl** are closed.
+    pub fn bind(addr: impl ToSocketAddrs, ttl: Duration) -> Result<Self> {
+        let http = tiny_http::Server::http(addr)
+            .map_err(|e| NavigatorError::IOError(std::io::Error::other(e.to_string())))?;
+
+        Ok(Self {
+            http,
+            sessions: HashMap::new(),
+            next_id: 1,
+            ttl,
+        })
+    }
+
+    /// Returns address the server is bound to.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Serves requests until receiving fails.
+    ///
+    /// Failing to respond, e.g., as the client disconnected, is reported on stderr only.
+    pub fn run(&mut self) -> Result<()> {
+        loop {
+            let request = self.http.recv()?;
+            if let Err(e) = self.respond(request) {
+                eprintln!("error: cannot respond: {e}");
+            }
+        }
+    }
+
+    fn respond(&mut self, mut request: tiny_http::Request) -> Result<()> {
+        let json_header = tiny_http::Header::from_bytes("Content-Type", "application/json")
+            .expect("valid header");
+        if *request.method() != tiny_http::Method::Post {
+            let response = tiny_http::Response::from_string("")
+                .with_status_code(405)
+                .with_header(tiny_http::Header::from_bytes("Allow", "POST").expect("valid header"));
+            return Ok(request.respond(response)?);
+        }
+
+        let mut body = String::new();
+        let response = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => match serde_json::from_str::<Value>(&body) {
+                Ok(message) => self.handle(&message),
+                Err(e) => error_response(Value::Null, PARSE_ERROR, &e.to_string()),
+            },
+            Err(e) => error_response(Value::Null, PARSE_ERROR, &e.to_string()),
+        };
+
+        Ok(request.respond(
+            tiny_http::Response::from_string(response.to_string()).with_header(json_header),
+        )?)
+    }
+
+    /// Handles JSON-RPC request **message**, and returns the response.
+    pub fn handle(&mut self, message: &Value) -> Value {
+        self.expire();
+
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let method = match (message.get("jsonrpc"), message.get("method")) {
+            (Some(v), Some(Value::String(m))) if v == "2.0" => m.as_str(),
+            _ => return error_response(id, INVALID_REQUEST, "invalid request"),
+        };
+        let params = message.get("params").cloned().unwrap_or(json!({}));
+
+        match self.dispatch(method, &params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(e) => error_response(id, e.code, &e.message),
+        }
+    }
+
+    fn expire(&mut self) {
+        let ttl = self.ttl;
+        self.sessions.retain(|_, s| s.last_used.elapsed() <= ttl);
+    }
+
+    fn dispatch(&mut self, method: &str, params: &Value) -> std::result::Result<Value, RpcError> {
+        if method == "create_session" {
+            let program = params
+                .get("program")
+                .and_then(Value::as_str)
+                .ok_or_else(|| invalid_params("missing program"))?;
+            let args = match params.get("args") {
+                Some(args) => strings(args)?,
+                None => vec!["0".to_owned()],
+            };
+            let nav = Navigator::new(program, args)?;
+
+            let id = self.next_id;
+            self.next_id += 1;
+            self.sessions.insert(
+                id,
+                Session {
+                    nav,
+                    route: vec![],
+                    last_used: Instant::now(),
+                },
+            );
+
+            return Ok(json!({ "session": id }));
+        }
+
+        let id = params
+            .get("session")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| invalid_params("missing session"))?;
+        if method == "close_session" {
+            return match self.sessions.remove(&id) {
+                Some(_) => Ok(json!(true)),
+                None => Err(unknown_session(id)),
+            };
+        }
+        let session = self
+            .sessions
+            .get_mut(&id)
+            .ok_or_else(|| unknown_session(id))?;
+        session.last_used = Instant::now();
+        let project = params
+            .get("project")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        match method {
+            "route" => Ok(json!({ "route": session.route })),
+            "activate" => {
+                for facet in session.facets(params.get("facets"))? {
+                    if !session.route.contains(&facet) {
+                        session.route.push(facet);
+                    }
+                }
+                Ok(json!({ "route": session.route }))
+            }
+            "deactivate" => {
+                let facets = session.facets(params.get("facets"))?;
+                session.route.retain(|f| !facets.contains(f));
+                Ok(json!({ "route": session.route }))
+            }
+            "facets" => {
+                let facets = match params.get("weight").and_then(Value::as_str) {
+                    Some(weight) => {
+                        let mut w = match weight {
+                            "facets" => Weight::FacetCounting,
+                            "answer-sets" => Weight::AnswerSetCounting,
+                            _ => return Err(invalid_params(format!("unknown weight {weight}"))),
+                        };
+                        match project {
+                            true => {
+                                facet_weights_projecting(&mut w, &mut session.nav, &session.route)
+                            }
+                            _ => facet_weights(&mut w, &mut session.nav, &session.route),
+                        }
+                        .ok_or(NavigatorError::None)?
+                        .into_iter()
+                        .map(|(facet, weight)| json!({ "facet": facet, "weight": weight }))
+                        .collect::<Vec<_>>()
+                    }
+                    None => {
+                        let atoms = match project {
+                            true => session
+                                .nav
+                                .facet_inducing_atoms_projecting(session.route.iter()),
+                            _ => session.nav.facet_inducing_atoms(session.route.iter()),
+                        }
+                        .ok_or(NavigatorError::None)?;
+                        let mut atoms = atoms.into_iter().map(lex::repr).collect::<Vec<_>>();
+                        atoms.sort();
+                        atoms.into_iter().map(Value::String).collect()
+                    }
+                };
+                Ok(json!({ "route": session.route, "facets": facets }))
+            }
+            "models" => {
+                let offset = params
+                    .get("offset")
+                    .and_then(Value::as_u64)
+                    .map_or(0, |o| (o as usize).min(MAX_OFFSET));
+                let limit = params
+                    .get("limit")
+                    .and_then(Value::as_u64)
+                    .map_or(10, |l| (l as usize).min(MAX_LIMIT));
+                let models = match limit {
+                    0 => vec![],
+                    _ => session
+                        .nav
+                        .solutions_after(offset, Some(limit), session.route.iter())?
+                        .into_iter()
+                        .map(|m| {
+                            let mut m = m.into_iter().map(lex::repr).collect::<Vec<_>>();
+                            m.sort();
+                            m
+                        })
+                        .collect(),
+                };
+                Ok(json!({ "route": session.route, "offset": offset, "models": models }))
+            }
+            "sieve" => {
+                let targets = session.atoms(params.get("targets"))?;
+                let covered = session
+                    .nav
+                    .sieve_quiet(session.route.iter(), &targets, project)
+                    .ok_or(NavigatorError::None)?;
+                let uncovered = targets
+                    .iter()
+                    .filter(|t| !covered.contains(t))
+                    .collect::<Vec<_>>();
+                Ok(json!({ "covered": covered, "uncovered": uncovered }))
+            }
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("unknown method {method}"),
+            }),
+        }
+    }
+}
+
+impl Session {
+    /// Parses **facets** as list of facets over atoms of the ground program.
+    fn facets(&self, facets: Option<&Value>) -> std::result::Result<Vec<String>, RpcError> {
+        let facets = facets.ok_or_else(|| invalid_params("missing facets"))?;
+        let mut out = vec![];
+        for facet in strings(facets)? {
+            let facet = lex::parse_facet(&facet)?;
+            let atom = lex::repr(facet.symbol);
+            if self.nav.is_known(atom.clone()) != Some(true) {
+                return Err(invalid_params(format!("unknown atom {atom}")));
+            }
+            out.push(facet.to_string());
+        }
+
+        Ok(out)
+    }
+
+    /// Parses **atoms** as list of atoms of the ground program, i.e., positive facets.
+    fn atoms(&self, atoms: Option<&Value>) -> std::result::Result<Vec<String>, RpcError> {
+        let atoms = self.facets(atoms)?;
+        match atoms.iter().find(|a| a.starts_with('~')) {
+            Some(atom) => Err(invalid_params(format!("negated atom {atom}"))),
+            None => Ok(atoms),
+        }
+    }
+}
+
+fn strings(value: &Value) -> std::result::Result<Vec<String>, RpcError> {
+    value
+        .as_array()
+        .and_then(|xs| {
+            xs.iter()
+                .map(|x| x.as_str().map(str::to_owned))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| invalid_params("expected list of strings"))
+}
+
+fn unknown_session(id: u64) -> RpcError {
+    RpcError {
+        code: UNKNOWN_SESSION,
+        message: format!("unknown or expired session {id}"),
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "error": { "code": code, "message": message }, "id": id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    fn call(server: &mut Server, method: &str, params: Value) -> Value {
+        server.handle(&json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 }))
+    }
+
+    fn post(addr: SocketAddr, body: &str) -> Result<Value> {
+        let mut stream = TcpStream::connect(addr)?;
+        write!(
+            stream,
+            "POST /rpc HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let (_, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or(NavigatorError::None)?;
+
+        serde_json::from_str(body).map_err(|e| NavigatorError::InvalidInput(e.to_string()))
+    }
+
+    #[test]
+    fn navigate_session() -> Result<()> {
+        let mut server = Server::bind("127.0.0.1:0", Duration::from_secs(60))?;
+
+        let r = call(
+            &mut server,
+            "create_session",
+            json!({ "program": "a;b. c;d :- b. e." }),
+        );
+        let session = r["result"]["session"]
+            .as_u64()
+            .ok_or(NavigatorError::None)?;
+
+        let r = call(&mut server, "facets", json!({ "session": session }));
+        assert_eq!(r["result"]["facets"], json!(["a", "b", "c", "d"]));
+
+        let r = call(
+            &mut server,
+            "activate",
+            json!({ "session": session, "facets": ["~a"] }),
+        );
+        assert_eq!(r["result"]["route"], json!(["~a"]));
+
+        let r = call(
+            &mut server,
+            "facets",
+            json!({ "session": session, "weight": "answer-sets" }),
+        );
+        assert_eq!(r["result"]["facets"].as_array().map(|x| x.len()), Some(4));
+
+        let r = call(
+            &mut server,
+            "models",
+            json!({ "session": session, "offset": 1, "limit": 5 }),
+        );
+        assert_eq!(r["result"]["models"].as_array().map(|x| x.len()), Some(1));
+
+        let r = call(
+            &mut server,
+            "sieve",
+            json!({ "session": session, "targets": ["a", "c"] }),
+        );
+        assert_eq!(r["result"]["covered"], json!(["c"]));
+        assert_eq!(r["result"]["uncovered"], json!(["a"]));
+        let r = call(
+            &mut server,
+            "sieve",
+            json!({ "session": session, "targets": ["~a"] }),
+        );
+        assert_eq!(r["error"]["code"], json!(INVALID_PARAMS));
+
+        let r = call(
+            &mut server,
+            "models",
+            json!({ "session": session, "offset": u64::MAX, "limit": u64::MAX }),
+        );
+        assert_eq!(r["result"]["models"], json!([]));
+        assert_eq!(r["result"]["offset"], json!(MAX_OFFSET));
+
+        let r = call(
+            &mut server,
+            "deactivate",
+            json!({ "session": session, "facets": ["~a"] }),
+        );
+        assert_eq!(r["result"]["route"], json!([]));
+
+        let r = call(
+            &mut server,
+            "activate",
+            json!({ "session": session, "facets": ["x"] }),
+        );
+        assert_eq!(r["error"]["code"], json!(INVALID_PARAMS));
+
+        let r = call(&mut server, "close_session", json!({ "session": session }));
+        assert_eq!(r["result"], json!(true));
+        let r = call(&mut server, "route", json!({ "session": session }));
+        assert_eq!(r["error"]["code"], json!(UNKNOWN_SESSION));
+
+        Ok(())
+    }
+
+    #[test]
+    fn expire_sessions() -> Result<()> {
+        let mut server = Server::bind("127.0.0.1:0", Duration::ZERO)?;
+
+        let r = call(&mut server, "create_session", json!({ "program": "a;b." }));
+        let session = r["result"]["session"]
+            .as_u64()
+            .ok_or(NavigatorError::None)?;
+        std::thread::sleep(Duration::from_millis(5));
+
+        let r = call(&mut server, "route", json!({ "session": session }));
+        assert_eq!(r["error"]["code"], json!(UNKNOWN_SESSION));
+
+        Ok(())
+    }
+
+    #[test]
+    fn serve_local_client() -> Result<()> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let mut server = Server::bind("127.0.0.1:0", Duration::from_secs(60))?;
+            let _ = tx.send(server.local_addr());
+            server.run()
+        });
+        let addr = rx.recv().ok().flatten().ok_or(NavigatorError::None)?;
+
+        let r = post(
+            addr,
+            r#"{"jsonrpc": "2.0", "method": "create_session", "params": {"program": "a;b."}, "id": 7}"#,
+        )?;
+        assert_eq!(r["id"], json!(7));
+        let session = r["result"]["session"]
+            .as_u64()
+            .ok_or(NavigatorError::None)?;
+
+        let body = json!({
+            "jsonrpc": "2.0",
+            "method": "facets",
+            "params": { "session": session },
+            "id": 8
+        });
+        let r = post(addr, &body.to_string())?;
+        assert_eq!(r["result"]["facets"], json!(["a", "b"]));
+
+        let r = post(addr, "{")?;
+        assert_eq!(r["error"]["code"], json!(PARSE_ERROR));
+
+        // clients disconnecting before the response do not stop the server
+        let mut stream = TcpStream::connect(addr)?;
+        write!(stream, "POST /rpc HTTP/1.1\r\nContent-Length: 1\r\n\r\n{{")?;
+        stream.shutdown(std::net::Shutdown::Both)?;
+        drop(stream);
+        let r = post(addr, &body.to_string())?;
+        assert_eq!(r["id"], json!(8));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! End-to-end runs of the `savan` binary.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Writes **program** to a file of its own, named after **name**.
+fn program(name: &str, program: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("savan-cli-{}-{name}.lp", std::process::id()));
+    std::fs::write(&path, program).unwrap();
+
+    path
+}
+
+/// Runs `savan` with **args**, and returns whether it succeeded together with its output.
+fn savan(args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_savan"))
+        .args(args)
+        .output()
+        .unwrap();
+
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    )
+}
+
+#[test]
+fn navigate_via_cli() {
+    let path = program("navigate", "a;b. c;d :- b. e.");
+    let file = path.to_str().unwrap();
+
+    let (ok, out) = savan(&["facets", file, "--route", "~a"]);
+    assert!(ok);
+    assert_eq!(out, "c\nd\n");
+
+    let (ok, out) = savan(&["brave", file, "--route", "b", "--format", "json"]);
+    assert!(ok);
+    let json = serde_json::from_str::<serde_json::Value>(&out).unwrap();
+    assert_eq!(json["brave"], serde_json::json!(["b", "c", "d", "e"]));
+    assert_eq!(json["route"], serde_json::json!(["b"]));
+
+    let (ok, out) = savan(&["enumerate", file, "-r", "a", "--format", "json"]);
+    assert!(ok);
+    let json = serde_json::from_str::<serde_json::Value>(&out).unwrap();
+    assert_eq!(json["solutions"], serde_json::json!([["a", "e"]]));
+
+    let (ok, _) = savan(&["brave", file, "--route", "f"]);
+    assert!(!ok);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn report_optimum_via_cli() {
+    let path = program("optimum", "a;b. c;d :- b. e. :~ a. [2@1] :~ c. [1@1]");
+    let file = path.to_str().unwrap();
+
+    let (ok, out) = savan(&["cautious", file, "--optimal"]);
+    assert!(ok);
+    assert_eq!(out, "b\nd\ne\noptimum: 0\n");
+
+    let (ok, out) = savan(&["cautious", file]);
+    assert!(ok);
+    assert_eq!(out, "e\n");
+    std::fs::remove_file(path).unwrap();
+}
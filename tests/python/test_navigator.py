@@ -0,0 +1,43 @@
+import pytest
+import savan
+
+PROGRAM = "a;b. c;d :- b. e."
+
+
+def test_consequences():
+    nav = savan.Navigator(PROGRAM)
+    assert nav.brave_consequences() == {"a", "b", "c", "d", "e"}
+    assert nav.cautious_consequences() == {"e"}
+    assert nav.facet_inducing_atoms(["~a"]) == {"c", "d"}
+    assert nav.truth_values(["a", "b", "e"], ["b"]) == {"a": "F", "b": "T", "e": "T"}
+    assert nav.brave_consequences(["a", "b"]) == set()
+
+
+def test_counting():
+    nav = savan.Navigator(PROGRAM)
+    assert nav.count() == 3
+    assert nav.count(["b"]) == 2
+    assert nav.count(weight=savan.Weight.FacetCounting) == 8
+    assert nav.weights(["b"], savan.Weight.AnswerSetCounting) == [
+        ("c", 1),
+        ("~c", 1),
+        ("d", 1),
+        ("~d", 1),
+    ]
+
+
+def test_models_and_sieve():
+    nav = savan.Navigator(PROGRAM)
+    assert len(nav.models()) == 3
+    assert nav.models(["b", "c"]) == [["b", "c", "e"]]
+    assert sorted(nav.sieve(["a", "c"], ["~a"])) == ["c"]
+
+
+def test_errors():
+    with pytest.raises(savan.NavigatorError):
+        savan.Navigator("a :- .")
+    nav = savan.Navigator(PROGRAM)
+    with pytest.raises(savan.NavigatorError):
+        nav.sieve(["~a"])
+    with pytest.raises(savan.NavigatorError):
+        nav.sieve(["f"])